@@ -0,0 +1,117 @@
+use common::{ParseError, Solution};
+use serde_json::{json, Value};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// min, median and max of a stage's run times
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn of(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "min_ns": self.min.as_nanos() as u64,
+            "median_ns": self.median.as_nanos() as u64,
+            "max_ns": self.max.as_nanos() as u64,
+        })
+    }
+}
+
+pub struct Timings {
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+}
+
+// time parsing and each part separately, each over the given number of iterations
+pub fn bench<S: Solution>(input: &str, iterations: usize) -> Result<Timings, ParseError> {
+    let solution = S::parse(input)?;
+
+    let parse = time(iterations, || S::parse(input));
+    let part_one = time(iterations, || solution.part_one());
+    let part_two = time(iterations, || solution.part_two());
+
+    Ok(Timings {
+        parse,
+        part_one,
+        part_two,
+    })
+}
+
+fn time<T>(iterations: usize, f: impl Fn() -> T) -> Stats {
+    let samples = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::of(samples)
+}
+
+pub fn print_table(timings: &[(usize, Timings)]) {
+    println!(
+        "{:>3}  {:<8}  {:>12}  {:>12}  {:>12}",
+        "day", "stage", "min", "median", "max"
+    );
+    for (day, timings) in timings {
+        for (stage, stats) in [
+            ("parse", &timings.parse),
+            ("part 1", &timings.part_one),
+            ("part 2", &timings.part_two),
+        ] {
+            println!(
+                "{day:>3}  {stage:<8}  {:>12}  {:>12}  {:>12}",
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max),
+            );
+        }
+    }
+}
+
+pub fn to_json(iterations: usize, timings: &[(usize, Timings)]) -> Value {
+    json!({
+        "iterations": iterations,
+        "days": timings
+            .iter()
+            .map(|(day, timings)| json!({
+                "day": day,
+                "parse": timings.parse.to_json(),
+                "part_one": timings.part_one.to_json(),
+                "part_two": timings.part_two.to_json(),
+            }))
+            .collect::<Vec<Value>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::of(
+            [5, 1, 3, 2, 4]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+        );
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+}
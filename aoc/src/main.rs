@@ -0,0 +1,360 @@
+// Advent of Code 2025: runner for every day
+// Usage: `cargo run -p aoc -- run <day> <input-file> [<extra-args>...]`
+//        `cargo run -p aoc -- run --all [--verify] [<input-dir>]`
+//
+// Anything after `<input-file>`, flags included, is passed on to the day, like
+// `--size <positions> --start <position>` for day 1 or the number of pairs for day 8.
+//
+// With `--all`, each day's input is read from `<input-dir>/day-NN/input.txt`,
+// where `<input-dir>` defaults to the current directory. Adding `--verify`
+// checks every answer against `<input-dir>/answers.toml` instead of printing it,
+// and exits non-zero if any of them don't match.
+//
+//        `cargo run --release -p aoc -- bench [--iterations <n>] [--json <file>] [<day>] [<input-dir>]`
+//
+// Times parsing, part 1 and part 2 separately for every day with an input in
+// `<input-dir>` (or just the one day given), reporting the min, median and max
+// over `<n>` iterations (10 by default), and optionally writing them out as JSON.
+
+mod bench;
+mod verify;
+
+use common::{ParseError, Solution};
+use day_01::dial::{DEFAULT_SIZE, DEFAULT_START};
+use day_01::Day01;
+use day_02::Day02;
+use day_03::Day03;
+use day_04::Day04;
+use day_05::Day05;
+use day_06::Day06;
+use day_07::Day07;
+use day_08::Day08;
+use day_09::Day09;
+use day_10::Day10;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+use verify::{DayStatus, KnownAnswers};
+
+const DAYS: usize = 10;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        _ => usage(),
+    }
+}
+
+pub struct Answers {
+    pub part_one: String,
+    pub part_two: String,
+}
+
+fn run(args: &[String]) {
+    // the day's own arguments start after the second positional, <day> <input-file>
+    let mut positionals = 0;
+    let day_args_start = args
+        .iter()
+        .position(|arg| {
+            if !arg.starts_with("--") {
+                positionals += 1;
+            }
+            positionals == 2
+        })
+        .map_or(args.len(), |i| i + 1);
+    let (args, extra_args) = args.split_at(day_args_start);
+
+    let (flags, args): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|arg| arg.starts_with("--"));
+    let all = flags.iter().any(|flag| *flag == "--all");
+    let verify = flags.iter().any(|flag| *flag == "--verify");
+    if let Some(flag) = flags
+        .iter()
+        .find(|flag| !matches!(flag.as_str(), "--all" | "--verify"))
+    {
+        eprintln!("unknown flag {flag}");
+        usage();
+    }
+
+    if all {
+        if !extra_args.is_empty() {
+            eprintln!("--all doesn't take extra arguments");
+            usage();
+        }
+        let input_dir = args.first().map(PathBuf::from).unwrap_or_default();
+        if verify {
+            verify_all(&input_dir);
+        } else {
+            run_all(&input_dir);
+        }
+    } else if verify {
+        eprintln!("--verify needs --all");
+        usage();
+    } else {
+        let day = parse_day(args.first().unwrap_or_else(|| usage()));
+        let input_filename = args.get(1).unwrap_or_else(|| usage());
+        match run_day(day, Path::new(input_filename), extra_args) {
+            Ok(answers) => print_answers(&answers),
+            Err(err) => {
+                eprintln!("{err}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn run_all(input_dir: &Path) {
+    let mut failed = false;
+    for day in 1..=DAYS {
+        let input_filename = input_path(input_dir, day);
+        if !input_filename.exists() {
+            println!(
+                "Day {day}: skipped, no input at {}",
+                input_filename.display()
+            );
+            continue;
+        }
+        println!("Day {day}:");
+        match run_day(day, &input_filename, &[]) {
+            Ok(answers) => print_answers(&answers),
+            Err(err) => {
+                eprintln!("{err}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn verify_all(input_dir: &Path) {
+    let known = KnownAnswers::load(&input_dir.join("answers.toml")).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+
+    let statuses: Vec<(usize, DayStatus)> = (1..=DAYS)
+        .map(|day| {
+            let input_filename = input_path(input_dir, day);
+            let status = if !input_filename.exists() {
+                DayStatus::Skipped
+            } else {
+                match run_day(day, &input_filename, &[]) {
+                    Ok(answers) => {
+                        let (part_one, part_two) = known.check(day, &answers);
+                        DayStatus::Checked(part_one, part_two)
+                    }
+                    Err(err) => DayStatus::Error(err.to_string()),
+                }
+            };
+            (day, status)
+        })
+        .collect();
+
+    verify::print_table(&statuses);
+
+    if statuses.iter().any(|(_, status)| status.is_failure()) {
+        process::exit(1);
+    }
+}
+
+fn bench(args: &[String]) {
+    let mut iterations = 10;
+    let mut json_filename: Option<PathBuf> = None;
+    let mut day: Option<usize> = None;
+    let mut input_dir = PathBuf::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => {
+                iterations = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| *n > 0)
+                    .unwrap_or_else(|| {
+                        eprintln!("--iterations needs a positive number");
+                        usage();
+                    });
+            }
+            "--json" => json_filename = Some(args.next().unwrap_or_else(|| usage()).into()),
+            flag if flag.starts_with("--") => {
+                eprintln!("unknown flag {flag}");
+                usage();
+            }
+            arg if day.is_none() && arg.parse::<usize>().is_ok() => day = Some(parse_day(arg)),
+            arg => input_dir = arg.into(),
+        }
+    }
+
+    let days: Vec<usize> = day.map_or_else(|| (1..=DAYS).collect(), |day| vec![day]);
+    let mut timings = vec![];
+    for day in days {
+        let input_filename = input_path(&input_dir, day);
+        if !input_filename.exists() {
+            continue;
+        }
+        let input = read_input(&input_filename);
+        match bench_day(day, &input, iterations) {
+            Ok(day_timings) => timings.push((day, day_timings)),
+            Err(err) => {
+                eprintln!("{}", err.with_file(&input_filename));
+                process::exit(1);
+            }
+        }
+    }
+
+    bench::print_table(&timings);
+
+    if let Some(json_filename) = json_filename {
+        let json = bench::to_json(iterations, &timings);
+        fs::write(&json_filename, format!("{json:#}\n")).expect("failed to write json");
+    }
+}
+
+fn bench_day(day: usize, input: &str, iterations: usize) -> Result<bench::Timings, ParseError> {
+    match day {
+        1 => bench::bench::<Day01>(input, iterations),
+        2 => bench::bench::<Day02>(input, iterations),
+        3 => bench::bench::<Day03>(input, iterations),
+        4 => bench::bench::<Day04>(input, iterations),
+        5 => bench::bench::<Day05>(input, iterations),
+        6 => bench::bench::<Day06>(input, iterations),
+        7 => bench::bench::<Day07>(input, iterations),
+        8 => bench::bench::<Day08>(input, iterations),
+        9 => bench::bench::<Day09>(input, iterations),
+        10 => bench::bench::<Day10>(input, iterations),
+        _ => unreachable!(),
+    }
+}
+
+fn run_day(
+    day: usize,
+    input_filename: &Path,
+    extra_args: &[String],
+) -> Result<Answers, ParseError> {
+    let input = read_input(input_filename);
+
+    solve_day(day, &input, extra_args).map_err(|err| err.with_file(input_filename))
+}
+
+// the whole input file, exiting with the path and the reason if it can't be read
+fn read_input(input_filename: &Path) -> String {
+    fs::read_to_string(input_filename).unwrap_or_else(|err| {
+        eprintln!("failed to read {}: {err}", input_filename.display());
+        process::exit(1);
+    })
+}
+
+fn solve_day(day: usize, input: &str, extra_args: &[String]) -> Result<Answers, ParseError> {
+    let answers = match day {
+        1 => {
+            let (size, start) = dial_flags(extra_args);
+            solve(Day01::parse(input)?.with_dial(size, start))
+        }
+        2 => solve(Day02::parse(input)?),
+        3 => solve(Day03::parse(input)?),
+        4 => solve(Day04::parse(input)?),
+        5 => solve(Day05::parse(input)?),
+        6 => solve(Day06::parse(input)?),
+        7 => solve(Day07::parse(input)?),
+        8 => {
+            let day = Day08::parse(input)?;
+            match pairs_arg(extra_args) {
+                Some(pairs) => solve(day.with_pairs(pairs)),
+                None => solve(day),
+            }
+        }
+        9 => solve(Day09::parse(input)?),
+        10 => solve(Day10::parse(input)?),
+        _ => unreachable!(),
+    };
+
+    Ok(answers)
+}
+
+// day 1's --size and --start, with the real puzzle's dial by default
+fn dial_flags(extra_args: &[String]) -> (isize, isize) {
+    let (mut size, mut start) = (DEFAULT_SIZE, DEFAULT_START);
+    let mut args = extra_args.iter();
+    while let Some(flag) = args.next() {
+        let value: Option<isize> = args.next().and_then(|value| value.parse().ok());
+        match (flag.as_str(), value) {
+            ("--size", Some(value)) if value > 0 => size = value,
+            ("--start", Some(value)) => start = value,
+            _ => {
+                eprintln!("day 1 takes --size <positive integer> and --start <integer>");
+                process::exit(2);
+            }
+        }
+    }
+    (size, start)
+}
+
+// day 8's optional number of pairs to connect for part 1
+fn pairs_arg(extra_args: &[String]) -> Option<usize> {
+    let pairs = extra_args.first()?;
+    match pairs.parse() {
+        Ok(pairs) => Some(pairs),
+        Err(_) => {
+            eprintln!("day 8 takes the number of pairs to connect as an integer, got {pairs:?}");
+            process::exit(2);
+        }
+    }
+}
+
+fn solve<S: Solution>(solution: S) -> Answers {
+    Answers {
+        part_one: solution.part_one().to_string(),
+        part_two: solution.part_two().to_string(),
+    }
+}
+
+fn print_answers(answers: &Answers) {
+    println!("part 1: {}", answers.part_one);
+    println!("part 2: {}", answers.part_two);
+}
+
+fn input_path(input_dir: &Path, day: usize) -> PathBuf {
+    input_dir.join(format!("day-{day:02}")).join("input.txt")
+}
+
+fn parse_day(day: &str) -> usize {
+    match day.parse() {
+        Ok(day) if (1..=DAYS).contains(&day) => day,
+        _ => {
+            eprintln!("day must be a number from 1 to {DAYS}, got {day:?}");
+            process::exit(2);
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: aoc run <day> <input-file> [<extra-args>...]");
+    eprintln!("       aoc run --all [--verify] [<input-dir>]");
+    eprintln!("       aoc bench [--iterations <n>] [--json <file>] [<day>] [<input-dir>]");
+    process::exit(2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_one_takes_dial_flags() {
+        let input = include_str!("../../day-01/example.txt");
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        let answers = solve_day(1, input, &[]).unwrap();
+        assert_eq!(
+            (answers.part_one.as_str(), answers.part_two.as_str()),
+            ("3", "6")
+        );
+
+        let answers = solve_day(1, input, &args(&["--size", "10", "--start", "0"])).unwrap();
+        assert_eq!(answers.part_two, "45");
+    }
+}
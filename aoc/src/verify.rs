@@ -0,0 +1,245 @@
+use crate::{Answers, DAYS};
+use common::ParseError;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use toml::{Table, Value};
+
+// known-correct answers keyed by day, loaded from a file like:
+//
+// [day-01]
+// part_one = 1182
+// part_two = 6907
+//
+// [day-10]
+// part_one = 475
+// part_two = "18273"
+//
+// values may be integers or strings, and are compared against each part's
+// Display output, so a part can be left out until its answer is known
+pub struct KnownAnswers {
+    days: HashMap<usize, KnownDay>,
+}
+
+#[derive(Default)]
+struct KnownDay {
+    part_one: Option<String>,
+    part_two: Option<String>,
+}
+
+impl KnownAnswers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("{}: failed to read answers: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let table: Table = text.parse().map_err(|err| format!("{err}"))?;
+        let mut days = HashMap::new();
+        let mut headers: HashMap<usize, &str> = HashMap::new();
+
+        for (key, value) in table {
+            let header = section_header(text, &key);
+            let day = key
+                .strip_prefix("day-")
+                .and_then(|day| day.parse::<usize>().ok())
+                .filter(|day| (1..=DAYS).contains(day))
+                .ok_or_else(|| {
+                    ParseError::at(
+                        text,
+                        header,
+                        format!("expected a section like [day-01] for a day from 1 to {DAYS}"),
+                    )
+                    .to_string()
+                })?;
+            // [day-1] and [day-01] are different keys to toml, so the later of the two
+            // would quietly replace the other's answers
+            if let Some(other) = headers.insert(day, header) {
+                let later = if other.as_ptr() > header.as_ptr() {
+                    other
+                } else {
+                    header
+                };
+                return Err(ParseError::at(
+                    text,
+                    later,
+                    format!("expected one section for day {day}"),
+                )
+                .to_string());
+            }
+            let Value::Table(parts) = value else {
+                return Err(format!("expected [{key}] to be a section of answers"));
+            };
+
+            let mut known = KnownDay::default();
+            for (part, answer) in parts {
+                let answer = match answer {
+                    Value::String(answer) => answer,
+                    Value::Integer(answer) => answer.to_string(),
+                    _ => return Err(format!("expected {key}.{part} to be an integer or string")),
+                };
+                match part.as_str() {
+                    "part_one" => known.part_one = Some(answer),
+                    "part_two" => known.part_two = Some(answer),
+                    _ => return Err(format!("expected part_one or part_two, found {key}.{part}")),
+                }
+            }
+            days.insert(day, known);
+        }
+
+        Ok(Self { days })
+    }
+
+    pub fn check(&self, day: usize, answers: &Answers) -> (Outcome, Outcome) {
+        let known = self.days.get(&day);
+        (
+            Outcome::of(known.and_then(|k| k.part_one.as_deref()), &answers.part_one),
+            Outcome::of(known.and_then(|k| k.part_two.as_deref()), &answers.part_two),
+        )
+    }
+}
+
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Unknown,
+}
+
+impl Outcome {
+    fn of(expected: Option<&str>, actual: &str) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Outcome::Pass,
+            Some(expected) => Outcome::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+            None => Outcome::Unknown,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {expected}, got {actual})")
+            }
+            Outcome::Unknown => write!(f, "no answer"),
+        }
+    }
+}
+
+pub enum DayStatus {
+    Checked(Outcome, Outcome),
+    Error(String),
+    Skipped,
+}
+
+impl DayStatus {
+    pub fn is_failure(&self) -> bool {
+        match self {
+            DayStatus::Checked(part_one, part_two) => {
+                part_one.is_failure() || part_two.is_failure()
+            }
+            DayStatus::Error(_) => true,
+            DayStatus::Skipped => false,
+        }
+    }
+}
+
+pub fn print_table(statuses: &[(usize, DayStatus)]) {
+    println!("{:>3}  {:<12}  part 2", "day", "part 1");
+    for (day, status) in statuses {
+        match status {
+            DayStatus::Checked(part_one, part_two) => {
+                println!("{day:>3}  {:<12}  {part_two}", part_one.to_string())
+            }
+            DayStatus::Error(err) => println!("{day:>3}  ERROR: {err}"),
+            DayStatus::Skipped => println!("{day:>3}  skipped, no input"),
+        }
+    }
+}
+
+// the [key] line that opens a section, so errors can point at it, or nothing at the
+// start of the text if it's written some other way
+fn section_header<'a>(text: &'a str, key: &str) -> &'a str {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or(line).trim())
+        .find(|line| {
+            line.strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+                .is_some_and(|name| name.trim() == key)
+        })
+        .unwrap_or(&text[..0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_answers() {
+        let known = KnownAnswers::parse(
+            r#"
+            [day-01]
+            part_one = 3
+            part_two = 6
+
+            [day-10]
+            part_one = "7"
+            "#,
+        )
+        .unwrap();
+
+        let answers = Answers {
+            part_one: "3".into(),
+            part_two: "5".into(),
+        };
+        let (part_one, part_two) = known.check(1, &answers);
+        assert!(matches!(part_one, Outcome::Pass));
+        assert!(part_two.is_failure());
+
+        let answers = Answers {
+            part_one: "7".into(),
+            part_two: "33".into(),
+        };
+        let (part_one, part_two) = known.check(10, &answers);
+        assert!(matches!(part_one, Outcome::Pass));
+        assert!(matches!(part_two, Outcome::Unknown));
+    }
+
+    #[test]
+    fn reject_unknown_sections() {
+        assert!(KnownAnswers::parse("[one]\npart_one = 1").is_err());
+        assert!(KnownAnswers::parse("[day-01]\npart_three = 1").is_err());
+
+        let err = KnownAnswers::parse("[day-01]\npart_one = 1\n\n[day-11]\npart_one = 2");
+        assert_eq!(
+            err.err().unwrap(),
+            "4:1: expected a section like [day-01] for a day from 1 to 10, found \"[day-11]\""
+        );
+        assert!(KnownAnswers::parse("[day-00]\npart_one = 1").is_err());
+    }
+
+    #[test]
+    fn reject_repeated_days() {
+        // whichever way round they're written, the second section is the one reported
+        for text in [
+            "[day-01]\npart_one = 1\n\n[day-1]\npart_one = 2",
+            "[day-1]\npart_one = 1\n\n[day-01]\npart_one = 2",
+        ] {
+            let err = KnownAnswers::parse(text).err().unwrap();
+            assert!(
+                err.starts_with("4:1: expected one section for day 1"),
+                "{err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::parse::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// a rectangular grid of cells stored row by row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [
+    (-1, 0), // N
+    (0, 1),  // E
+    (1, 0),  // S
+    (0, -1), // W
+];
+
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, 0),  // N
+    (-1, 1),  // NE
+    (0, 1),   // E
+    (1, 1),   // SE
+    (1, 0),   // S
+    (1, -1),  // SW
+    (0, -1),  // W
+    (-1, -1), // NW
+];
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "grid data must fill every cell");
+        Self { data, rows, cols }
+    }
+
+    pub fn filled(rows: usize, cols: usize, val: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(rows, cols, vec![val; rows * cols])
+    }
+
+    // parse one row per line, turning each char into a cell with to_cell, which
+    // returns None for chars that aren't valid cells; every row must be the same width
+    pub fn parse_with(
+        input: &str,
+        to_cell: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = input.trim_end_matches(['\n', '\r']).lines().collect();
+        let cols = lines.first().map_or(0, |line| line.chars().count());
+        if cols == 0 {
+            return Err(ParseError::at(
+                input,
+                &input[..0],
+                "expected a row of cells",
+            ));
+        }
+
+        let mut data = Vec::with_capacity(lines.len() * cols);
+        for line in &lines {
+            if line.chars().count() != cols {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected a row {cols} cells wide"),
+                ));
+            }
+            for (at, c) in line.char_indices() {
+                let cell = to_cell(c).ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        &line[at..at + c.len_utf8()],
+                        format!("expected {expected}"),
+                    )
+                })?;
+                data.push(cell);
+            }
+        }
+
+        Ok(Self::new(lines.len(), cols, data))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn coord_to_index(&self, row: isize, col: isize) -> Option<usize> {
+        if row < 0 || row >= self.rows as isize || col < 0 || col >= self.cols as isize {
+            return None;
+        }
+        Some(row as usize * self.cols + col as usize)
+    }
+
+    pub fn index_to_coord(&self, index: usize) -> (usize, usize) {
+        (index / self.cols, index % self.cols)
+    }
+
+    // bounds-checked access, None for anything off the edge of the grid
+    pub fn get(&self, row: isize, col: isize) -> Option<&T> {
+        self.coord_to_index(row, col).map(|index| &self.data[index])
+    }
+
+    pub fn get_mut(&mut self, row: isize, col: isize) -> Option<&mut T> {
+        self.coord_to_index(row, col)
+            .map(|index| &mut self.data[index])
+    }
+
+    // sets the cell if it's on the grid, otherwise does nothing
+    pub fn set(&mut self, row: isize, col: isize, val: T) {
+        if let Some(cell) = self.get_mut(row, col) {
+            *cell = val;
+        }
+    }
+
+    // access that wraps around the edges, as if the grid were tiled infinitely
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        let row = row.rem_euclid(self.rows as isize) as usize;
+        let col = col.rem_euclid(self.cols as isize) as usize;
+        &self[(row, col)]
+    }
+
+    // the coordinates of the up to 4 orthogonal neighbours that are on the grid
+    pub fn neighbours_4(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(row, col, &NEIGHBOURS_4)
+    }
+
+    // the coordinates of the up to 8 orthogonal and diagonal neighbours that are on the grid
+    pub fn neighbours_8(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(row, col, &NEIGHBOURS_8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        row: usize,
+        col: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |(d_row, d_col)| {
+            let (row, col) = (row as isize + d_row, col as isize + d_col);
+            self.coord_to_index(row, col)
+                .map(|_| (row as usize, col as usize))
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(col < self.cols, "column {col} is off the grid");
+        self.data[col..].iter().step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.data.chunks_exact(self.cols)
+    }
+
+    pub fn iter_columns(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = &T>> + '_ {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    // every cell along with its (row, col) coordinate, row by row
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.index_to_coord(index), cell))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) is off the grid"
+        );
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) is off the grid"
+        );
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse_with(input, |c| c.to_digit(10), "a digit")
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(format!("{grid}"), "123\n456\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = digits("123\n45\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.token, "45");
+
+        let err = digits("123\n4x6\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "expected a digit");
+    }
+
+    #[test]
+    fn bounds_checked_and_wrapping_access() {
+        let mut grid = digits("123\n456").unwrap();
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(1, 3), None);
+        assert_eq!(grid.get(1, 2), Some(&6));
+        assert_eq!(*grid.get_wrapping(-1, 3), 4);
+
+        grid.set(0, 0, 9);
+        grid.set(5, 5, 9);
+        assert_eq!(format!("{grid}"), "923\n456\n");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = digits("123\n456\n789").unwrap();
+        let corner: Vec<_> = grid.neighbours_4(0, 0).collect();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours_8(0, 0).count(), 3);
+        assert_eq!(grid.neighbours_8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        let sums: Vec<u32> = grid.iter_columns().map(|col| col.sum()).collect();
+        assert_eq!(sums, vec![5, 7, 9]);
+        assert_eq!(grid.iter_rows().count(), 2);
+        assert_eq!(grid.cells().nth(4), Some(((1, 1), &5)));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod grid;
+pub mod parse;
+pub mod range_set;
+pub mod solution;
+
+pub use grid::Grid;
+pub use parse::ParseError;
+pub use range_set::RangeSet;
+pub use solution::Solution;
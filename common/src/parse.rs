@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// where a puzzle input failed to parse, with 1-based line and column numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // token must be a slice of input so we can work out where it sits
+    pub fn at(input: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = offset_of(input, token);
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = input[line_start..offset].chars().count() + 1;
+
+        Self {
+            file: None,
+            line,
+            column,
+            token: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn with_file(self, file: impl Into<PathBuf>) -> Self {
+        Self {
+            file: Some(file.into()),
+            ..self
+        }
+    }
+
+    // an error reported against s, which is a slice of input, is moved so that
+    // its position is relative to the whole input instead
+    pub fn relative_to(self, input: &str, s: &str) -> Self {
+        let start = Self::at(input, s, "");
+        let column = if self.line == 1 {
+            start.column + self.column - 1
+        } else {
+            self.column
+        };
+
+        Self {
+            line: start.line + self.line - 1,
+            column,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}:{}: {}, ", self.line, self.column, self.message)?;
+        if self.token.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found {:?}", self.token)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// parse a token that must be a slice of input, reporting its position on failure
+pub fn parse_token<T: FromStr>(input: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, format!("expected {expected}")))
+}
+
+// check that every character of s, a slice of input, is allowed, reporting the first that isn't
+pub fn expect_chars(
+    input: &str,
+    s: &str,
+    allowed: impl Fn(char) -> bool,
+    expected: &str,
+) -> Result<(), ParseError> {
+    match s.char_indices().find(|(_, c)| !allowed(*c)) {
+        Some((at, c)) => Err(ParseError::at(
+            input,
+            &s[at..at + c.len_utf8()],
+            format!("expected {expected}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn offset_of(input: &str, token: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+    assert!(
+        token_start >= start && token_start + token.len() <= start + input.len(),
+        "token must be a slice of the input"
+    );
+    token_start - start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_token() {
+        let input = "1,2\n3,x4\n";
+        let err = ParseError::at(input, &input[6..8], "expected an integer");
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.token, "x4");
+    }
+
+    #[test]
+    fn display() {
+        let input = "12\nab";
+        let err = parse_token::<usize>(input, &input[3..], "an integer")
+            .unwrap_err()
+            .with_file("input.txt");
+        assert_eq!(
+            format!("{err}"),
+            "input.txt:2:1: expected an integer, found \"ab\""
+        );
+    }
+
+    #[test]
+    fn relative_to() {
+        let input = "first\nsecond line";
+        let line = &input[6..];
+        let err = ParseError::at(line, &line[7..], "expected a number").relative_to(input, line);
+        assert_eq!((err.line, err.column), (2, 8));
+    }
+}
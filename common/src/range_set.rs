@@ -0,0 +1,212 @@
+use std::ops::RangeInclusive;
+
+// a set of integers stored as sorted, disjoint ranges. touching ranges like 1-3 and
+// 4-5 are merged, so every gap between stored ranges holds at least one integer
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    ranges: Vec<RangeInclusive<usize>>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    // empty ranges (with start > end) are ignored
+    pub fn insert(&mut self, range: RangeInclusive<usize>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // every stored range from first to last overlaps or touches the new one
+        let first = self
+            .ranges
+            .partition_point(|r| r.end().saturating_add(1) < start);
+        let last = self
+            .ranges
+            .partition_point(|r| *r.start() <= end.saturating_add(1));
+
+        let merged = if first < last {
+            start.min(*self.ranges[first].start())..=end.max(*self.ranges[last - 1].end())
+        } else {
+            start..=end
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<usize>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // every stored range from first to last overlaps the removed one, and only
+        // the parts sticking out either side of it survive
+        let first = self.ranges.partition_point(|r| *r.end() < start);
+        let last = self.ranges.partition_point(|r| *r.start() <= end);
+        if first == last {
+            return;
+        }
+
+        let mut pieces = vec![];
+        if *self.ranges[first].start() < start {
+            pieces.push(*self.ranges[first].start()..=start - 1);
+        }
+        if *self.ranges[last - 1].end() > end {
+            pieces.push(end + 1..=*self.ranges[last - 1].end());
+        }
+        self.ranges.splice(first..last, pieces);
+    }
+
+    // a binary search over the stored ranges
+    pub fn contains(&self, value: usize) -> bool {
+        let i = self.ranges.partition_point(|r| *r.end() < value);
+        i < self.ranges.len() && *self.ranges[i].start() <= value
+    }
+
+    // the number of integers in the set, which is one too many for a usize when the
+    // set holds every usize
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|r| (r.end() - r.start()) as u128 + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // the disjoint ranges in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = &RangeInclusive<usize>> {
+        self.ranges.iter()
+    }
+
+    // the ranges missing between the lowest and highest values in the set
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<usize>> + '_ {
+        self.ranges
+            .windows(2)
+            .map(|pair| pair[0].end() + 1..=pair[1].start() - 1)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            if start <= end {
+                intersection.push(start..=end);
+            }
+            // whichever range ends first can't overlap anything else
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self {
+            ranges: intersection,
+        }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range.clone());
+        }
+        difference
+    }
+
+    // everything within bounds that isn't in the set
+    pub fn complement(&self, bounds: RangeInclusive<usize>) -> Self {
+        Self::from_iter([bounds]).difference(self)
+    }
+}
+
+impl FromIterator<RangeInclusive<usize>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<usize>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &RangeSet) -> Vec<RangeInclusive<usize>> {
+        set.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_touching_ranges() {
+        let mut set = RangeSet::from_iter([10..=14, 3..=5, 16..=20, 12..=18]);
+        assert_eq!(ranges(&set), vec![3..=5, 10..=20]);
+
+        set.insert(6..=9);
+        assert_eq!(ranges(&set), vec![3..=20]);
+        assert_eq!(set.len(), 18);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(7..=2);
+        assert_eq!(ranges(&set), vec![3..=20]);
+    }
+
+    #[test]
+    fn remove_splits_ranges() {
+        let mut set = RangeSet::from_iter([3..=5, 10..=20, 25..=30]);
+        set.remove(12..=14);
+        set.remove(18..=26);
+        set.remove(0..=3);
+        assert_eq!(ranges(&set), vec![4..=5, 10..=11, 15..=17, 27..=30]);
+        assert_eq!(
+            set.gaps().collect::<Vec<_>>(),
+            vec![6..=9, 12..=14, 18..=26]
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let set = RangeSet::from_iter([3..=5, 10..=14, 16..=20, 12..=18]);
+        for value in [3, 5, 10, 15, 20] {
+            assert!(set.contains(value));
+        }
+        for value in [0, 2, 6, 9, 21] {
+            assert!(!set.contains(value));
+        }
+        assert!(!RangeSet::new().contains(0));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = RangeSet::from_iter([0..=10, 20..=30]);
+        let b = RangeSet::from_iter([5..=25, 40..=40]);
+
+        assert_eq!(ranges(&a.union(&b)), vec![0..=30, 40..=40]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![5..=10, 20..=25]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..=4, 26..=30]);
+        assert_eq!(ranges(&b.complement(0..=50)), vec![0..=4, 26..=39, 41..=50]);
+        assert_eq!(
+            ranges(&a.complement(usize::MAX - 1..=usize::MAX)),
+            vec![usize::MAX - 1..=usize::MAX]
+        );
+        assert_eq!(
+            RangeSet::new().complement(0..=usize::MAX).len(),
+            usize::MAX as u128 + 1
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use crate::parse::ParseError;
+use std::path::Path;
+use std::{fmt, process};
+
+// a day's puzzle: parse the input once, then answer each part from the parsed form
+pub trait Solution: Sized {
+    type PartOne: fmt::Display;
+    type PartTwo: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self, ParseError>;
+
+    fn part_one(&self) -> Self::PartOne;
+
+    fn part_two(&self) -> Self::PartTwo;
+}
+
+// parse the input read from input_filename, or report where it was malformed and exit
+pub fn parse_or_exit<S: Solution>(input: &str, input_filename: impl AsRef<Path>) -> S {
+    S::parse(input).unwrap_or_else(|err| {
+        eprintln!("{}", err.with_file(input_filename.as_ref()));
+        process::exit(1);
+    })
+}
@@ -0,0 +1,199 @@
+// the real puzzle's dial has 100 positions, starting at 50
+pub const DEFAULT_SIZE: isize = 100;
+pub const DEFAULT_START: isize = 50;
+
+pub struct Dial {
+    pub val: isize,
+    pub zeros_seen: isize,
+    pub zeros_landed_upon: isize,
+    size: isize,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dial {
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> DialBuilder {
+        DialBuilder {
+            size: DEFAULT_SIZE,
+            start: DEFAULT_START,
+        }
+    }
+
+    // the number of positions, numbered 0 to size - 1
+    pub fn size(&self) -> isize {
+        self.size
+    }
+
+    pub fn turn(&mut self, amount: isize) {
+        // start by counting how many full rotations we would do, which would
+        // generally pass by 0, unless you start and end on 0 which we will handle later
+        let size = self.size;
+        let full_rotations = amount.abs() / size;
+        self.zeros_seen += full_rotations;
+
+        // this is what is left over after any full rotations
+        let extra = amount % size;
+
+        // handle the case where you're starting and ending on 0 because the final
+        // zero will be counted at the end and we don't want to duplicate it
+        if self.val == 0 && extra == 0 && full_rotations > 0 {
+            self.zeros_seen -= 1;
+        }
+
+        let sum = self.val + extra;
+
+        // if we started at 0 then anything less than size would not pass 0 again
+        // but from any other starting value we could pass 0 again if the sum goes
+        // negative, or over size
+        if self.val != 0 && !(0..=size).contains(&sum) {
+            self.zeros_seen += 1;
+        }
+
+        // this is the amount we actually want to rotate the dial ignoring full rotations
+        let remainder = sum % size;
+
+        self.val = if remainder < 0 {
+            remainder + size
+        } else {
+            remainder
+        };
+
+        // a turn of 0 leaves us on zero without any click passing it
+        if self.val == 0 {
+            self.zeros_landed_upon += 1;
+            if amount != 0 {
+                self.zeros_seen += 1;
+            }
+        }
+    }
+
+    // the slow but obviously correct version of turn, moving one click at a time
+    // and counting every click that points at zero
+    pub fn turn_by_clicks(&mut self, amount: isize) {
+        for _ in 0..amount.abs() {
+            self.val = (self.val + amount.signum()).rem_euclid(self.size);
+            if self.val == 0 {
+                self.zeros_seen += 1;
+            }
+        }
+        if self.val == 0 {
+            self.zeros_landed_upon += 1;
+        }
+    }
+
+    pub fn reset(&mut self, value: isize) {
+        self.val = value.rem_euclid(self.size);
+        self.zeros_seen = 0;
+        self.zeros_landed_upon = 0;
+    }
+}
+
+pub struct DialBuilder {
+    size: isize,
+    start: isize,
+}
+
+impl DialBuilder {
+    // panics when the dial is built if size isn't positive
+    pub fn size(self, size: isize) -> Self {
+        Self { size, ..self }
+    }
+
+    // any position, which wraps around onto the dial
+    pub fn start(self, start: isize) -> Self {
+        Self { start, ..self }
+    }
+
+    pub fn build(self) -> Dial {
+        assert!(self.size > 0, "a dial needs a positive size");
+        Dial {
+            val: self.start.rem_euclid(self.size),
+            zeros_seen: 0,
+            zeros_landed_upon: 0,
+            size: self.size,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_turn() {
+        let mut dial = Dial::new();
+        let tests: [(isize, isize, isize, isize, isize); 10] = [
+            (50, 50, 0, 1, 1),   // starting non-zero, landing on zero, no extra rotations
+            (50, 150, 0, 2, 1),  // starting non-zero. landing on zero, one extra rotation
+            (0, 100, 0, 1, 1),   // starting on zero, landing on zero, exactly one rotation
+            (0, -100, 0, 1, 1),  // negative the same scenario
+            (99, 2, 1, 1, 0),    // crossed zero so one zero seen
+            (1, -2, 99, 1, 0),   // crossed zero so one zero seen
+            (99, 102, 1, 2, 0),  // one full rotation plus crossed zero
+            (1, -102, 99, 2, 0), // one full rotation plus crossed zero
+            (0, 500, 0, 5, 1),   // five full rotations but starting and ending on zero
+            (0, -501, 99, 5, 0), // five full rotations plus one more
+        ];
+
+        for (start, amount, end, zeros_seen, zeros_landed_upon) in tests {
+            dial.reset(start);
+            dial.turn(amount);
+            assert_eq!(dial.val, end);
+            assert_eq!(dial.zeros_seen, zeros_seen);
+            assert_eq!(dial.zeros_landed_upon, zeros_landed_upon);
+        }
+    }
+
+    #[test]
+    fn test_turn_other_sizes() {
+        let tests: [(isize, isize, isize, isize, isize, isize); 6] = [
+            (10, 5, 5, 0, 1, 1),     // landing on zero
+            (10, 5, -27, 8, 3, 0),   // two full rotations plus crossed zero
+            (10, 0, 30, 0, 3, 1),    // starting and ending on zero
+            (1, 0, 7, 0, 7, 1),      // every click lands on zero
+            (7, 3, 4, 0, 1, 1),      // landing on zero on an odd size
+            (1000, 999, 2, 1, 1, 0), // crossed zero on a big dial
+        ];
+
+        for (size, start, amount, end, zeros_seen, zeros_landed_upon) in tests {
+            let mut dial = Dial::builder().size(size).start(start).build();
+            dial.turn(amount);
+            assert_eq!(dial.val, end);
+            assert_eq!(dial.zeros_seen, zeros_seen);
+            assert_eq!(dial.zeros_landed_upon, zeros_landed_upon);
+        }
+
+        // starts wrap onto the dial
+        assert_eq!(Dial::builder().size(10).start(-3).build().val, 7);
+    }
+
+    proptest! {
+        #[test]
+        fn turn_matches_turn_by_clicks(
+            size in prop_oneof![1isize..200, 1isize..=3],
+            start in -1000isize..1000,
+            // small turns make landing on zero and turns of zero likely enough to test
+            amounts in prop::collection::vec(prop_oneof![-1000isize..1000, -3isize..=3], 0..50),
+        ) {
+            let mut dial = Dial::builder().size(size).start(start).build();
+            let mut reference = Dial::builder().size(size).start(start).build();
+
+            for amount in amounts {
+                dial.turn(amount);
+                reference.turn_by_clicks(amount);
+                prop_assert_eq!(dial.val, reference.val);
+                prop_assert_eq!(dial.zeros_seen, reference.zeros_seen);
+                prop_assert_eq!(dial.zeros_landed_upon, reference.zeros_landed_upon);
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+pub mod dial;
+pub mod solver;
+pub mod trace;
+
+use common::parse::parse_token;
+use common::{ParseError, Solution};
+use dial::{Dial, DEFAULT_SIZE, DEFAULT_START};
+use solver::{Edit, Target};
+use trace::Step;
+
+pub struct Day01 {
+    amounts: Vec<isize>,
+    size: isize,
+    start: isize,
+}
+
+impl Day01 {
+    // other combination lock puzzles can use a different dial
+    pub fn with_dial(self, size: isize, start: isize) -> Self {
+        Self {
+            size,
+            start,
+            ..self
+        }
+    }
+
+    fn turn_dial(&self) -> Dial {
+        let mut dial = Dial::builder().size(self.size).start(self.start).build();
+        for amount in &self.amounts {
+            dial.turn(*amount);
+        }
+        dial
+    }
+
+    // every turn of the dial, with how it moved and what it counted
+    pub fn trace(&self) -> Vec<Step> {
+        let mut dial = Dial::builder().size(self.size).start(self.start).build();
+        trace::trace(&mut dial, &self.amounts)
+    }
+
+    // every start position that would make the instructions hit the target
+    pub fn starts_matching(&self, target: Target) -> Vec<isize> {
+        solver::starts_matching(&self.amounts, self.size, target)
+    }
+
+    // every change to a single instruction, to at most max_amount either way, that
+    // would make the instructions hit the target from the usual start
+    pub fn edits_matching(&self, target: Target, max_amount: isize) -> Vec<Edit> {
+        solver::edits_matching(&self.amounts, self.size, self.start, target, max_amount)
+    }
+}
+
+// an instruction like L68 or R48, which must be a slice of input, as a signed amount
+pub(crate) fn parse_instruction(input: &str, line: &str) -> Result<isize, ParseError> {
+    let direction_len = line.chars().next().map_or(0, char::len_utf8);
+    let (direction, magnitude_str) = line.split_at(direction_len);
+    let sign = match direction {
+        "L" => -1,
+        "R" => 1,
+        _ => return Err(ParseError::at(input, direction, "expected L or R")),
+    };
+    let magnitude: isize = parse_token(input, magnitude_str, "an integer")?;
+    Ok(sign * magnitude)
+}
+
+impl Solution for Day01 {
+    type PartOne = isize;
+    type PartTwo = isize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let amounts = input
+            .trim()
+            .split('\n')
+            .map(|line| parse_instruction(input, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            amounts,
+            size: DEFAULT_SIZE,
+            start: DEFAULT_START,
+        })
+    }
+
+    // the number of zeros landed upon
+    fn part_one(&self) -> isize {
+        self.turn_dial().zeros_landed_upon
+    }
+
+    // the number of zeros seen, including passing by zero mid-turn
+    fn part_two(&self) -> isize {
+        self.turn_dial().zeros_seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day01::parse(EXAMPLE).unwrap().part_one(), 3);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day01::parse(EXAMPLE).unwrap().part_two(), 6);
+    }
+}
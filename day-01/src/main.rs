@@ -1,122 +1,179 @@
 // Advent of Code 2025: Day 1
 // https://adventofcode.com/2025/day/1
-// Usage: `cargo run <input-file>
-
-use std::{env, fs};
+// Usage: `cargo run [--size <positions>] [--start <position>] [--trace <file>] <input-file>`
+//    or: `cargo run replay [--size <positions>] <trace-file>`
+//    or: `cargo run search [--size <positions>] [--start <position>]
+//             (--landed <count> | --seen <count>) [--max-amount <clicks>] <input-file>`
+//
+// --trace writes every turn of the dial to a .csv or .json file, and replay re-runs
+// such a file and points out the first turn that doesn't match the recording.
+// search lists the start positions, and the changes to a single instruction of at
+// most --max-amount clicks (the dial size by default), that hit the target count.
+
+use common::solution::{parse_or_exit, Solution};
+use day_01::dial::{DEFAULT_SIZE, DEFAULT_START};
+use day_01::solver::Target;
+use day_01::trace::{self, Instruction};
+use day_01::Day01;
+use std::path::Path;
+use std::{env, fs, process};
 
 fn main() {
-    let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let input = fs::read_to_string(input_filename).expect("failed to read input");
-    let mut dial = Dial::new();
-
-    input.trim().split('\n').for_each(|line| {
-        let (direction, magnitude_str) = line.split_at(1);
-        let magnitude: isize = magnitude_str.parse().expect("input is a valid int");
-        let amount = if direction == "L" {
-            -magnitude
-        } else {
-            magnitude
-        };
-
-        dial.turn(amount);
-    });
-
-    println!(
-        "The number of zeros landed upon was: {}",
-        dial.zeros_landed_upon
-    );
-    println!("The number of zeros seen was: {}", dial.zeros_seen);
-}
-
-pub struct Dial {
-    val: isize,
-    zeros_seen: isize,
-    zeros_landed_upon: isize,
-}
-
-impl Dial {
-    fn new() -> Self {
-        Dial {
-            val: 50,
-            zeros_seen: 0,
-            zeros_landed_upon: 0,
+    let mut size = DEFAULT_SIZE;
+    let mut start = DEFAULT_START;
+    let mut trace_filename: Option<String> = None;
+    let mut target: Option<Target> = None;
+    let mut max_amount: Option<isize> = None;
+    let mut input_filename: Option<String> = None;
+
+    let mut args = env::args().skip(1).peekable();
+    let command = args.next_if(|arg| arg == "replay" || arg == "search");
+    let (replaying, searching) = match command.as_deref() {
+        Some("replay") => (true, false),
+        Some("search") => (false, true),
+        _ => (false, false),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = flag_value(&arg, args.next()),
+            "--start" if !replaying => start = flag_value(&arg, args.next()),
+            "--trace" if !replaying && !searching => {
+                trace_filename = Some(args.next().unwrap_or_else(|| usage()))
+            }
+            "--landed" if searching && target.is_none() => {
+                target = Some(Target::ZerosLandedUpon(flag_value(&arg, args.next())))
+            }
+            "--seen" if searching && target.is_none() => {
+                target = Some(Target::ZerosSeen(flag_value(&arg, args.next())))
+            }
+            "--max-amount" if searching => max_amount = Some(flag_value(&arg, args.next())),
+            _ if input_filename.is_none() => input_filename = Some(arg),
+            _ => usage(),
         }
     }
+    if size <= 0 {
+        eprintln!("--size must be positive");
+        usage();
+    }
 
-    pub fn turn(&mut self, amount: isize) {
-        // start by counting how many full rotations we would do, which would
-        // generally pass by 0, unless you start and end on 0 which we will handle later
-        let full_rotations = amount.abs() / 100;
-        self.zeros_seen += full_rotations;
-
-        // this is what is left over after any full rotations
-        let extra = amount % 100;
-
-        // handle the case where you're starting and ending on 0 because the final
-        // zero will be counted at the end and we don't want to duplicate it
-        if self.val == 0 && extra == 0 && full_rotations > 0 {
-            self.zeros_seen -= 1;
-        }
+    if replaying {
+        replay(&input_filename.unwrap_or_else(|| usage()), size);
+    }
 
-        let sum = self.val + extra;
+    let input_filename = input_filename.expect("please supply an input filename");
+    let input = fs::read_to_string(&input_filename).expect("failed to read input");
+    let day: Day01 = parse_or_exit(&input, &input_filename);
+    let day = day.with_dial(size, start);
+
+    if searching {
+        let target = target.unwrap_or_else(|| {
+            eprintln!("search needs a target of --landed or --seen");
+            usage();
+        });
+        search(&day, target, max_amount.unwrap_or(size));
+    }
 
-        // if we started at 0 then anything less than 100 would not pass 0 again
-        // but from any other starting value we could pass 0 again if the sum goes
-        // negative, or over 100
-        if self.val != 0 {
-            if sum > 100 || sum < 0 {
-                self.zeros_seen += 1;
+    if let Some(trace_filename) = trace_filename {
+        let steps = day.trace();
+        let output = match Path::new(&trace_filename).extension() {
+            Some(extension) if extension == "json" => trace::to_json(&steps).to_string(),
+            Some(extension) if extension == "csv" => trace::to_csv(&steps),
+            _ => {
+                eprintln!("--trace needs a .csv or .json file");
+                usage();
             }
-        }
+        };
+        fs::write(&trace_filename, output).expect("failed to write trace");
+    }
 
-        // this is the amount we actually want to rotate the dial ignoring full rotations
-        let remainder = sum % 100;
+    println!("The number of zeros landed upon was: {}", day.part_one());
+    println!("The number of zeros seen was: {}", day.part_two());
+}
 
-        self.val = if remainder < 0 {
-            remainder + 100
+// print the replayed turns, exiting with 1 if they stopped matching the trace
+fn replay(trace_filename: &str, size: isize) -> ! {
+    let input = fs::read_to_string(trace_filename).expect("failed to read trace");
+    let recorded = trace::parse_trace(&input).unwrap_or_else(|err| {
+        eprintln!("{}", err.with_file(trace_filename));
+        process::exit(1);
+    });
+
+    let (replayed, divergence) = trace::replay(&recorded, size);
+    for (i, step) in replayed.iter().enumerate() {
+        let marker = if divergence.as_ref().is_some_and(|d| d.step == i) {
+            ">>"
         } else {
-            remainder
+            "  "
         };
-
-        if self.val == 0 {
-            self.zeros_landed_upon += 1;
-            self.zeros_seen += 1;
-        }
+        println!("{marker} {:>4} {step}", i + 1);
     }
 
-    pub fn reset(&mut self, value: isize) {
-        self.val = value;
-        self.zeros_seen = 0;
-        self.zeros_landed_upon = 0;
+    match divergence {
+        None => {
+            println!("all {} turns matched the trace", recorded.len());
+            process::exit(0);
+        }
+        Some(divergence) => {
+            println!(
+                "turn {} ({}) diverged from the trace:",
+                divergence.step + 1,
+                Instruction(divergence.recorded.amount)
+            );
+            for (field, recorded, replayed) in divergence.differences() {
+                println!("    {field}: recorded {recorded}, replayed {replayed}");
+            }
+            process::exit(1);
+        }
     }
 }
 
-#[cfg(test)]
-pub mod test {
-    use super::*;
+fn search(day: &Day01, target: Target, max_amount: isize) -> ! {
+    let starts = day.starts_matching(target);
+    let starts = starts
+        .iter()
+        .map(|start| start.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+    if starts.is_empty() {
+        println!("no start hits the target");
+    } else {
+        println!("starts that hit the target: {starts}");
+    }
 
-    #[test]
-    fn test_turn() {
-        let mut dial = Dial::new();
-        let tests: [(isize, isize, isize, isize, isize); 10] = [
-            (50, 50, 0, 1, 1),   // starting non-zero, landing on zero, no extra rotations
-            (50, 150, 0, 2, 1),  // starting non-zero. landing on zero, one extra rotation
-            (0, 100, 0, 1, 1),   // starting on zero, landing on zero, exactly one rotation
-            (0, -100, 0, 1, 1),  // negative the same scenario
-            (99, 2, 1, 1, 0),    // crossed zero so one zero seen
-            (1, -2, 99, 1, 0),   // crossed zero so one zero seen
-            (99, 102, 1, 2, 0),  // one full rotation plus crossed zero
-            (1, -102, 99, 2, 0), // one full rotation plus crossed zero
-            (0, 500, 0, 5, 1),   // five full rotations but starting and ending on zero
-            (0, -501, 99, 5, 0), // five full rotations plus one more
-        ];
+    let edits = day.edits_matching(target, max_amount);
+    println!(
+        "single instruction changes that hit the target: {}",
+        edits.len()
+    );
+    for edit in edits {
+        println!(
+            "{:>6}: {} -> {}",
+            edit.index + 1,
+            Instruction(edit.from),
+            Instruction(edit.to)
+        );
+    }
+    process::exit(0);
+}
 
-        for (start, amount, end, zeros_seen, zeros_landed_upon) in tests {
-            dial.reset(start);
-            dial.turn(amount);
-            assert_eq!(dial.val, end);
-            assert_eq!(dial.zeros_seen, zeros_seen);
-            assert_eq!(dial.zeros_landed_upon, zeros_landed_upon);
+fn flag_value(flag: &str, value: Option<String>) -> isize {
+    match value.map(|value| value.parse()) {
+        Some(Ok(value)) => value,
+        _ => {
+            eprintln!("{flag} needs an integer value");
+            usage();
         }
     }
 }
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: day-01 [--size <positions>] [--start <position>] [--trace <file>] <input-file>"
+    );
+    eprintln!("       day-01 replay [--size <positions>] <trace-file>");
+    eprintln!(
+        "       day-01 search [--size <positions>] [--start <position>] \
+         (--landed <count> | --seen <count>) [--max-amount <clicks>] <input-file>"
+    );
+    process::exit(2);
+}
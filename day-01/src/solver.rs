@@ -0,0 +1,234 @@
+use crate::dial::Dial;
+use std::ops::Add;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub zeros_seen: isize,
+    pub zeros_landed_upon: isize,
+}
+
+impl Add for Counts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            zeros_seen: self.zeros_seen + other.zeros_seen,
+            zeros_landed_upon: self.zeros_landed_upon + other.zeros_landed_upon,
+        }
+    }
+}
+
+// the count we want a list of instructions to end up with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    ZerosSeen(isize),
+    ZerosLandedUpon(isize),
+}
+
+impl Target {
+    pub fn matches(&self, counts: &Counts) -> bool {
+        match self {
+            Self::ZerosSeen(target) => counts.zeros_seen == *target,
+            Self::ZerosLandedUpon(target) => counts.zeros_landed_upon == *target,
+        }
+    }
+}
+
+// the counts from turning a dial of the given size through amounts, for every start
+// position at once, indexed by the start.
+//
+// with offsets P_k = a_1 + ... + a_k the dial is at s + P_k after k turns, so it lands
+// on zero whenever P_k = -s mod size, and a turn from s + x to s + y passes zero once
+// for each multiple of size it crosses. that's a difference of floor((s + c) / size)
+// terms, and each of those is a constant plus one more for every s >= size - c mod size,
+// so the counts for all starts come from a histogram and a prefix sum rather than
+// turning the dial once per start
+pub fn counts_for_every_start(amounts: &[isize], size: isize) -> Vec<Counts> {
+    assert!(size > 0, "a dial needs a positive size");
+    let positions = size as usize;
+
+    let mut landed = vec![0; positions];
+    let mut seen_constant = 0;
+    let mut seen_steps = vec![0; positions];
+
+    // adds sign * floor((s + c) / size) to the zeros seen from every start s
+    let mut add_floor = |c: isize, sign: isize| {
+        seen_constant += sign * c.div_euclid(size);
+        let r = c.rem_euclid(size);
+        if r > 0 {
+            seen_steps[(size - r) as usize] += sign;
+        }
+    };
+
+    let mut offset = 0;
+    for amount in amounts {
+        let (x, y) = (offset, offset + amount);
+        if *amount > 0 {
+            // clicks land on x + 1 ..= y
+            add_floor(y, 1);
+            add_floor(x, -1);
+        } else if *amount < 0 {
+            // clicks land on y ..= x - 1
+            add_floor(x - 1, 1);
+            add_floor(y - 1, -1);
+        }
+        offset = y;
+        landed[offset.rem_euclid(size) as usize] += 1;
+    }
+
+    let mut seen = seen_constant;
+    (0..positions)
+        .map(|start| {
+            seen += seen_steps[start];
+            Counts {
+                zeros_seen: seen,
+                zeros_landed_upon: landed[(size - start as isize).rem_euclid(size) as usize],
+            }
+        })
+        .collect()
+}
+
+// every start position that makes amounts hit the target
+pub fn starts_matching(amounts: &[isize], size: isize, target: Target) -> Vec<isize> {
+    counts_for_every_start(amounts, size)
+        .iter()
+        .enumerate()
+        .filter(|(_, counts)| target.matches(counts))
+        .map(|(start, _)| start as isize)
+        .collect()
+}
+
+// replacing the amount at index with another one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edit {
+    pub index: usize,
+    pub from: isize,
+    pub to: isize,
+}
+
+// every change of a single instruction to an amount within -max_amount..=max_amount
+// that makes amounts hit the target from start.
+//
+// working backwards we keep the counts from every position to the end of the list, so
+// trying an edit is one turn from where the dial was before it plus a lookup
+pub fn edits_matching(
+    amounts: &[isize],
+    size: isize,
+    start: isize,
+    target: Target,
+    max_amount: isize,
+) -> Vec<Edit> {
+    // where the dial is and what it has counted before each instruction
+    let mut dial = Dial::builder().size(size).start(start).build();
+    let mut before = vec![];
+    for amount in amounts {
+        before.push((dial.val, counts_of(&dial)));
+        dial.turn(*amount);
+    }
+
+    let mut edits = vec![];
+    let mut after = vec![Counts::default(); size as usize];
+    for (index, amount) in amounts.iter().enumerate().rev() {
+        let (position, counts_before) = before[index];
+        for to in -max_amount..=max_amount {
+            if to == *amount {
+                continue;
+            }
+            let (end, counts) = turn_from(size, position, to);
+            if target.matches(&(counts_before + counts + after[end as usize])) {
+                edits.push(Edit {
+                    index,
+                    from: *amount,
+                    to,
+                });
+            }
+        }
+
+        after = (0..size)
+            .map(|position| {
+                let (end, counts) = turn_from(size, position, *amount);
+                counts + after[end as usize]
+            })
+            .collect();
+    }
+
+    edits.reverse();
+    edits
+}
+
+fn counts_of(dial: &Dial) -> Counts {
+    Counts {
+        zeros_seen: dial.zeros_seen,
+        zeros_landed_upon: dial.zeros_landed_upon,
+    }
+}
+
+fn turn_from(size: isize, position: isize, amount: isize) -> (isize, Counts) {
+    let mut dial = Dial::builder().size(size).start(position).build();
+    dial.turn(amount);
+    (dial.val, counts_of(&dial))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: [isize; 10] = [-68, -30, 48, -5, 60, -55, -1, -99, 14, -82];
+
+    #[test]
+    fn example_starts() {
+        let counts = counts_for_every_start(&EXAMPLE, 100);
+        assert_eq!(
+            counts[50],
+            Counts {
+                zeros_seen: 6,
+                zeros_landed_upon: 3,
+            }
+        );
+
+        let starts = starts_matching(&EXAMPLE, 100, Target::ZerosLandedUpon(3));
+        assert!(starts.contains(&50));
+        assert!(starts
+            .iter()
+            .all(|start| counts[*start as usize].zeros_landed_upon == 3));
+    }
+
+    #[test]
+    fn example_edits() {
+        let edits = edits_matching(&EXAMPLE, 100, 50, Target::ZerosLandedUpon(4), 99);
+        // the last turn starts from 14, so L14 lands on zero one more time
+        assert!(edits.contains(&Edit {
+            index: 9,
+            from: -82,
+            to: -14,
+        }));
+
+        for edit in edits {
+            let mut amounts = EXAMPLE;
+            amounts[edit.index] = edit.to;
+            let mut dial = Dial::new();
+            for amount in amounts {
+                dial.turn(amount);
+            }
+            assert_eq!(dial.zeros_landed_upon, 4, "{edit:?}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn every_start_matches_turning_the_dial(
+            size in prop_oneof![1..=5isize, 1..=200isize],
+            amounts in prop::collection::vec(-500..=500isize, 0..20),
+        ) {
+            let counts = counts_for_every_start(&amounts, size);
+            for start in 0..size {
+                let mut dial = Dial::builder().size(size).start(start).build();
+                for amount in &amounts {
+                    dial.turn(*amount);
+                }
+                prop_assert_eq!(counts[start as usize], counts_of(&dial), "start {}", start);
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use crate::dial::Dial;
+use common::parse::parse_token;
+use common::ParseError;
+use serde_json::{json, Value};
+use std::fmt;
+
+const CSV_HEADER: &str = "instruction,start,end,full_rotations,zeros_seen,zeros_landed_upon";
+
+// what happened during one instruction, with counts for just that turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub amount: isize,
+    pub start: isize,
+    pub end: isize,
+    pub full_rotations: isize,
+    pub zeros_seen: isize,
+    pub zeros_landed_upon: isize,
+}
+
+impl Step {
+    // turn the dial, recording how it moved
+    pub fn turn(dial: &mut Dial, amount: isize) -> Self {
+        let (start, zeros_seen, zeros_landed_upon) =
+            (dial.val, dial.zeros_seen, dial.zeros_landed_upon);
+        dial.turn(amount);
+
+        Self {
+            amount,
+            start,
+            end: dial.val,
+            full_rotations: amount.abs() / dial.size(),
+            zeros_seen: dial.zeros_seen - zeros_seen,
+            zeros_landed_upon: dial.zeros_landed_upon - zeros_landed_upon,
+        }
+    }
+
+    fn fields(&self) -> [(&'static str, isize); 5] {
+        [
+            ("start", self.start),
+            ("end", self.end),
+            ("full_rotations", self.full_rotations),
+            ("zeros_seen", self.zeros_seen),
+            ("zeros_landed_upon", self.zeros_landed_upon),
+        ]
+    }
+}
+
+// the instruction as written in the puzzle input, like L68 or R48
+pub struct Instruction(pub isize);
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = if self.0 < 0 { 'L' } else { 'R' };
+        write!(f, "{direction}{}", self.0.abs())
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>6}: {:>3} -> {:>3}, {} full rotations, {} zeros seen, {} landed upon",
+            Instruction(self.amount).to_string(),
+            self.start,
+            self.end,
+            self.full_rotations,
+            self.zeros_seen,
+            self.zeros_landed_upon
+        )
+    }
+}
+
+pub fn trace(dial: &mut Dial, amounts: &[isize]) -> Vec<Step> {
+    amounts
+        .iter()
+        .map(|amount| Step::turn(dial, *amount))
+        .collect()
+}
+
+pub fn to_csv(steps: &[Step]) -> String {
+    let mut csv = format!("{CSV_HEADER}\n");
+    for step in steps {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            Instruction(step.amount),
+            step.start,
+            step.end,
+            step.full_rotations,
+            step.zeros_seen,
+            step.zeros_landed_upon
+        ));
+    }
+    csv
+}
+
+pub fn to_json(steps: &[Step]) -> Value {
+    Value::Array(
+        steps
+            .iter()
+            .map(|step| {
+                json!({
+                    "instruction": Instruction(step.amount).to_string(),
+                    "start": step.start,
+                    "end": step.end,
+                    "full_rotations": step.full_rotations,
+                    "zeros_seen": step.zeros_seen,
+                    "zeros_landed_upon": step.zeros_landed_upon,
+                })
+            })
+            .collect(),
+    )
+}
+
+// read a trace written by to_csv or to_json, telling them apart by the opening [
+pub fn parse_trace(input: &str) -> Result<Vec<Step>, ParseError> {
+    if input.trim_start().starts_with('[') {
+        parse_json(input)
+    } else {
+        parse_csv(input)
+    }
+}
+
+fn parse_csv(input: &str) -> Result<Vec<Step>, ParseError> {
+    let mut lines = input.lines().filter(|line| !line.trim().is_empty());
+    match lines.next() {
+        Some(header) if header.trim() == CSV_HEADER => {}
+        header => {
+            return Err(ParseError::at(
+                input,
+                header.unwrap_or(&input[..0]),
+                format!("expected the header {CSV_HEADER}"),
+            ))
+        }
+    }
+
+    lines
+        .map(|line| {
+            let values: Vec<&str> = line.trim().split(',').collect();
+            let [instruction, start, end, full_rotations, zeros_seen, zeros_landed_upon] =
+                values[..]
+            else {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "expected six comma separated values",
+                ));
+            };
+            Ok(Step {
+                amount: crate::parse_instruction(input, instruction)?,
+                start: parse_token(input, start, "an integer")?,
+                end: parse_token(input, end, "an integer")?,
+                full_rotations: parse_token(input, full_rotations, "an integer")?,
+                zeros_seen: parse_token(input, zeros_seen, "an integer")?,
+                zeros_landed_upon: parse_token(input, zeros_landed_upon, "an integer")?,
+            })
+        })
+        .collect()
+}
+
+fn parse_json(input: &str) -> Result<Vec<Step>, ParseError> {
+    let value: Value = serde_json::from_str(input).map_err(|err| ParseError {
+        file: None,
+        line: err.line(),
+        column: err.column(),
+        token: String::new(),
+        message: format!("expected a json trace ({err})"),
+    })?;
+    let whole = input.trim();
+    let steps = value
+        .as_array()
+        .ok_or_else(|| ParseError::at(input, whole, "expected an array of steps"))?;
+
+    steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let integer = |field: &str| {
+                step[field].as_i64().map(|v| v as isize).ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        whole,
+                        format!("expected step {i} to have an integer {field}"),
+                    )
+                })
+            };
+            let amount = step["instruction"]
+                .as_str()
+                .and_then(|instruction| crate::parse_instruction(instruction, instruction).ok())
+                .ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        whole,
+                        format!("expected step {i} to have an instruction like L68"),
+                    )
+                })?;
+            Ok(Step {
+                amount,
+                start: integer("start")?,
+                end: integer("end")?,
+                full_rotations: integer("full_rotations")?,
+                zeros_seen: integer("zeros_seen")?,
+                zeros_landed_upon: integer("zeros_landed_upon")?,
+            })
+        })
+        .collect()
+}
+
+// the first step where re-running a trace didn't match what was recorded
+#[derive(Debug, PartialEq, Eq)]
+pub struct Divergence {
+    pub step: usize,
+    pub recorded: Step,
+    pub replayed: Step,
+}
+
+impl Divergence {
+    // the fields that differ, with their recorded and replayed values
+    pub fn differences(&self) -> Vec<(&'static str, isize, isize)> {
+        self.recorded
+            .fields()
+            .into_iter()
+            .zip(self.replayed.fields())
+            .filter(|((_, recorded), (_, replayed))| recorded != replayed)
+            .map(|((name, recorded), (_, replayed))| (name, recorded, replayed))
+            .collect()
+    }
+}
+
+// re-run the recorded instructions on a dial of the given size, starting where the
+// recording did, and return every replayed step up to the first divergence if any
+pub fn replay(recorded: &[Step], size: isize) -> (Vec<Step>, Option<Divergence>) {
+    let start = recorded.first().map_or(0, |step| step.start);
+    let mut dial = Dial::builder().size(size).start(start).build();
+    let mut replayed = vec![];
+
+    for (i, recorded_step) in recorded.iter().enumerate() {
+        let step = Step::turn(&mut dial, recorded_step.amount);
+        replayed.push(step);
+        if step != *recorded_step {
+            return (
+                replayed,
+                Some(Divergence {
+                    step: i,
+                    recorded: *recorded_step,
+                    replayed: step,
+                }),
+            );
+        }
+    }
+
+    (replayed, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_trace() -> Vec<Step> {
+        trace(&mut Dial::new(), &[-68, -30, 48, -5, 60])
+    }
+
+    #[test]
+    fn trace_records_each_turn() {
+        let steps = example_trace();
+        assert_eq!(
+            steps[0],
+            Step {
+                amount: -68,
+                start: 50,
+                end: 82,
+                full_rotations: 0,
+                zeros_seen: 1,
+                zeros_landed_upon: 0,
+            }
+        );
+        assert_eq!(
+            (steps[2].start, steps[2].end, steps[2].zeros_landed_upon),
+            (52, 0, 1)
+        );
+    }
+
+    #[test]
+    fn csv_and_json_round_trip() {
+        let steps = example_trace();
+        assert_eq!(parse_trace(&to_csv(&steps)).unwrap(), steps);
+        assert_eq!(parse_trace(&to_json(&steps).to_string()).unwrap(), steps);
+
+        let err = parse_trace(&format!("{CSV_HEADER}\nL68,50,82,0,1\n")).unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (2, "expected six comma separated values")
+        );
+    }
+
+    #[test]
+    fn replay_finds_first_divergence() {
+        let mut steps = example_trace();
+        assert_eq!(replay(&steps, 100).1, None);
+
+        steps[3].zeros_seen += 1;
+        steps[4].end += 1;
+        let (replayed, divergence) = replay(&steps, 100);
+        let divergence = divergence.unwrap();
+        assert_eq!(divergence.step, 3);
+        assert_eq!(replayed.len(), 4);
+        assert_eq!(divergence.differences(), vec![("zeros_seen", 1, 0)]);
+    }
+}
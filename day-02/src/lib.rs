@@ -0,0 +1,177 @@
+use common::parse::parse_token;
+use common::{ParseError, RangeSet, Solution};
+use std::ops::RangeInclusive;
+
+pub struct Day02 {
+    // layer k holds the ids covered by more than k of the given ranges, so summing over
+    // every layer counts an id in two overlapping ranges once for each of them
+    coverage: Vec<RangeSet>,
+}
+
+impl Solution for Day02 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let ranges: Vec<RangeInclusive<usize>> = input
+            .trim()
+            .split(',')
+            .map(|range| {
+                let (start, end) = range
+                    .split_once('-')
+                    .ok_or_else(|| ParseError::at(input, range, "expected a range like 11-22"))?;
+                let start: usize = parse_token(input, start, "an integer")?;
+                let end: usize = parse_token(input, end, "an integer")?;
+                Ok(start..=end)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut coverage = vec![];
+        for range in ranges {
+            add_range(&mut coverage, range);
+        }
+        Ok(Self { coverage })
+    }
+
+    fn part_one(&self) -> usize {
+        self.ranges()
+            .flat_map(|range| find_invalid_part_1(range.clone()))
+            .sum()
+    }
+
+    fn part_two(&self) -> usize {
+        self.ranges()
+            .flat_map(|range| find_invalid_part_2(range.clone()))
+            .sum()
+    }
+}
+
+impl Day02 {
+    // the disjoint ranges of every coverage layer
+    fn ranges(&self) -> impl Iterator<Item = &RangeInclusive<usize>> {
+        self.coverage.iter().flat_map(|layer| layer.iter())
+    }
+}
+
+// the part of range already in layer k is what gets added to layer k + 1
+fn add_range(coverage: &mut Vec<RangeSet>, range: RangeInclusive<usize>) {
+    let mut added = RangeSet::from_iter([range]);
+    let mut layer = 0;
+    while !added.is_empty() {
+        if layer == coverage.len() {
+            coverage.push(RangeSet::new());
+        }
+        let deeper = coverage[layer].intersection(&added);
+        coverage[layer] = coverage[layer].union(&added);
+        added = deeper;
+        layer += 1;
+    }
+}
+
+// ids made of a block of digits repeated exactly twice, like 123123
+pub fn find_invalid_part_1(range: RangeInclusive<usize>) -> Vec<usize> {
+    repeated_block_ids(range, |repeats| repeats == 2)
+}
+
+// ids made of a block of digits repeated at least twice, like 121212 or 7777
+pub fn find_invalid_part_2(range: RangeInclusive<usize>) -> Vec<usize> {
+    repeated_block_ids(range, |repeats| repeats >= 2)
+}
+
+// every id in range made of a block of digits repeated some number of times that
+// repeats allows, in ascending order.
+//
+// repeating a b digit block r times is the same as multiplying it by 1 + 10^b + ... +
+// 10^(b(r-1)), so rather than scanning the range we work out which blocks land inside
+// it for each multiplier. a number like 111111 can be built from blocks of 1, 2 or 3
+// digits, so the results are deduplicated across block lengths
+fn repeated_block_ids(range: RangeInclusive<usize>, repeats: impl Fn(u32) -> bool) -> Vec<usize> {
+    let (start, end) = (*range.start() as u128, *range.end() as u128);
+    let mut ids = vec![];
+
+    for digits in digit_count(start)..=digit_count(end) {
+        for block_digits in 1..=digits / 2 {
+            if !digits.is_multiple_of(block_digits) || !repeats(digits / block_digits) {
+                continue;
+            }
+
+            let multiplier = (10u128.pow(digits) - 1) / (10u128.pow(block_digits) - 1);
+            let smallest_block = 10u128.pow(block_digits - 1);
+            let largest_block = 10u128.pow(block_digits) - 1;
+            let first = smallest_block.max(start.div_ceil(multiplier));
+            let last = largest_block.min(end / multiplier);
+            ids.extend((first..=last).map(|block| (block * multiplier) as usize));
+        }
+    }
+
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+fn digit_count(num: u128) -> u32 {
+    num.checked_ilog10().unwrap_or(0) + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day02::parse(EXAMPLE).unwrap().part_one(), 1227775554);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day02::parse(EXAMPLE).unwrap().part_two(), 4174379265);
+    }
+
+    #[test]
+    fn overlapping_ranges_count_each_id_per_range() {
+        // 22 is in both ranges
+        let day = Day02::parse("10-30,20-40").unwrap();
+        assert_eq!(day.part_one(), 11 + 22 + 22 + 33);
+
+        // 22 is in all three, and 33 in the last two
+        let day = Day02::parse("10-30,20-40,22-35").unwrap();
+        assert_eq!(day.part_one(), 11 + 22 * 3 + 33 * 2);
+        assert_eq!(day.coverage.len(), 3);
+    }
+
+    #[test]
+    fn matches_scanning_every_number() {
+        let repeated = |num: usize, twice_only: bool| {
+            let digits = num.to_string().into_bytes();
+            (1..=digits.len() / 2).any(|block| {
+                digits.len().is_multiple_of(block)
+                    && (!twice_only || digits.len() == 2 * block)
+                    && digits.chunks(block).all(|chunk| chunk == &digits[..block])
+            })
+        };
+
+        for range in [0..=0, 0..=10_000, 95..=115, 998_999..=1_012_000] {
+            let scanned = |twice_only| {
+                range
+                    .clone()
+                    .filter(|num| repeated(*num, twice_only))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(find_invalid_part_1(range.clone()), scanned(true));
+            assert_eq!(find_invalid_part_2(range.clone()), scanned(false));
+        }
+    }
+
+    #[test]
+    fn huge_ranges() {
+        // each id with 2n digits is one of the 9 * 10^(n-1) blocks of n digits repeated
+        let invalid = find_invalid_part_1(1..=10_000_000_000);
+        assert_eq!(invalid.len(), 9 + 90 + 900 + 9_000 + 90_000);
+        assert_eq!(invalid.last(), Some(&9_999_999_999));
+
+        let invalid = find_invalid_part_2(usize::MAX - 10_000_000_000..=usize::MAX);
+        assert_eq!(invalid, vec![18_446_744_071_844_674_407]);
+    }
+}
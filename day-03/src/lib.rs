@@ -0,0 +1,82 @@
+use common::parse::expect_chars;
+use common::{ParseError, Solution};
+
+pub struct Day03 {
+    banks: Vec<Vec<u8>>,
+}
+
+impl Solution for Day03 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let banks = input
+            .trim()
+            .split("\n")
+            .map(|bank| {
+                expect_chars(
+                    input,
+                    bank,
+                    |c| c.is_ascii_digit(),
+                    "a battery joltage from 0 to 9",
+                )?;
+                if bank.len() < 12 {
+                    return Err(ParseError::at(
+                        input,
+                        bank,
+                        "expected a bank of at least 12 batteries",
+                    ));
+                }
+                Ok(bank.as_bytes().to_vec())
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { banks })
+    }
+
+    // the total joltage with 2 batteries
+    fn part_one(&self) -> usize {
+        self.banks.iter().map(|bank| highest_joltage(bank, 2)).sum()
+    }
+
+    // the total joltage with 12 batteries
+    fn part_two(&self) -> usize {
+        self.banks
+            .iter()
+            .map(|bank| highest_joltage(bank, 12))
+            .sum()
+    }
+}
+
+pub fn highest_joltage(bank: &[u8], num_batteries: usize) -> usize {
+    let (at, val) = first_max(&bank[0..=bank.len() - num_batteries]);
+    if num_batteries == 1 {
+        val
+    } else {
+        val * 10usize.pow(num_batteries as u32 - 1)
+            + highest_joltage(&bank[at + 1..], num_batteries - 1)
+    }
+}
+
+fn first_max(bank: &[u8]) -> (usize, usize) {
+    let max_val = bank.iter().max().unwrap();
+    let at = bank.iter().position(|val| val == max_val).unwrap();
+    (at, (max_val - b'0').into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day03::parse(EXAMPLE).unwrap().part_one(), 357);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day03::parse(EXAMPLE).unwrap().part_two(), 3121910778619);
+    }
+}
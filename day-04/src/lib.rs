@@ -0,0 +1,98 @@
+use common::{Grid, ParseError, Solution};
+use std::fmt;
+
+pub struct Day04 {
+    grid: Grid<Cell>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Roll,
+    Moved,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Empty => write!(f, "."),
+            Cell::Roll => write!(f, "@"),
+            Cell::Moved => write!(f, "x"),
+        }
+    }
+}
+
+impl Solution for Day04 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_with(
+            input,
+            |c| match c {
+                '.' => Some(Cell::Empty),
+                '@' => Some(Cell::Roll),
+                _ => None,
+            },
+            "'.' or a roll of paper '@'",
+        )?;
+
+        Ok(Self { grid })
+    }
+
+    // the number of rolls moved in the first round
+    fn part_one(&self) -> usize {
+        move_rolls(&mut self.grid.clone())
+    }
+
+    // the total number of rolls moved once nothing else can be
+    fn part_two(&self) -> usize {
+        let mut grid = self.grid.clone();
+        let mut total_moved = 0;
+        let mut moved = move_rolls(&mut grid);
+
+        while moved > 0 {
+            total_moved += moved;
+            moved = move_rolls(&mut grid);
+        }
+
+        total_moved
+    }
+}
+
+pub fn move_rolls(grid: &mut Grid<Cell>) -> usize {
+    let movable: Vec<(usize, usize)> = grid
+        .cells()
+        .filter(|((row, col), cell)| {
+            let adjacent_rolls = grid
+                .neighbours_8(*row, *col)
+                .filter(|&coord| grid[coord] == Cell::Roll)
+                .count();
+
+            **cell == Cell::Roll && adjacent_rolls < 4
+        })
+        .map(|(coord, _)| coord)
+        .collect();
+    let moved = movable.len();
+    for coord in movable {
+        grid[coord] = Cell::Moved;
+    }
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day04::parse(EXAMPLE).unwrap().part_one(), 13);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day04::parse(EXAMPLE).unwrap().part_two(), 43);
+    }
+}
@@ -0,0 +1,76 @@
+use common::parse::parse_token;
+use common::{ParseError, RangeSet, Solution};
+use std::ops::RangeInclusive;
+
+pub struct Day05 {
+    fresh: RangeSet,
+    available: Vec<usize>,
+}
+
+impl Solution for Day05 {
+    type PartOne = usize;
+    type PartTwo = u128;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let trimmed = input.trim();
+        let (fresh_input, available_input) = trimmed.split_once("\n\n").ok_or_else(|| {
+            ParseError::at(
+                input,
+                &trimmed[trimmed.len()..],
+                "expected a blank line between the fresh ranges and the available ingredients",
+            )
+        })?;
+
+        let fresh_ranges: Vec<RangeInclusive<_>> = fresh_input
+            .split("\n")
+            .map(|range_str| {
+                let (start, end) = range_str
+                    .split_once("-")
+                    .ok_or_else(|| ParseError::at(input, range_str, "expected a range like 3-5"))?;
+                let start: usize = parse_token(input, start, "an integer")?;
+                let end: usize = parse_token(input, end, "an integer")?;
+                Ok(start..=end)
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        let available = available_input
+            .split("\n")
+            .map(|val| parse_token(input, val, "an integer"))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            fresh: fresh_ranges.into_iter().collect(),
+            available,
+        })
+    }
+
+    // the number of available ingredients that are fresh
+    fn part_one(&self) -> usize {
+        self.available
+            .iter()
+            .filter(|ingredient| self.fresh.contains(**ingredient))
+            .count()
+    }
+
+    // the total number of ingredient ids considered fresh
+    fn part_two(&self) -> u128 {
+        self.fresh.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day05::parse(EXAMPLE).unwrap().part_one(), 3);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day05::parse(EXAMPLE).unwrap().part_two(), 14);
+    }
+}
@@ -0,0 +1,168 @@
+use common::parse::expect_chars;
+use common::{ParseError, Solution};
+
+pub struct Day06 {
+    input: String,
+}
+
+impl Solution for Day06 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    // the worksheet is column-aligned, so each part reads the raw text its own way
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = input.trim_matches('\n').split('\n').collect();
+        let (operators, numbers) = lines.split_last().unwrap();
+        if numbers.is_empty() {
+            return Err(ParseError::at(
+                input,
+                operators,
+                "expected rows of numbers above the operators",
+            ));
+        }
+
+        let width = lines[0].len();
+        let problems = operators.split_ascii_whitespace().count();
+        expect_chars(
+            input,
+            operators,
+            |c| matches!(c, '+' | '*' | ' '),
+            "'+' or '*'",
+        )?;
+
+        for line in &lines {
+            if line.len() != width {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected a line {width} characters wide"),
+                ));
+            }
+        }
+
+        for line in numbers {
+            expect_chars(input, line, |c| c.is_ascii_digit() || c == ' ', "a digit")?;
+            if line.split_ascii_whitespace().count() != problems {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected {problems} numbers, one per problem"),
+                ));
+            }
+        }
+
+        Ok(Self {
+            input: input.to_string(),
+        })
+    }
+
+    fn part_one(&self) -> usize {
+        normal_math(&self.input)
+    }
+
+    fn part_two(&self) -> usize {
+        cephalopod_math(&self.input)
+    }
+}
+
+pub fn normal_math(input: &str) -> usize {
+    let maths: Vec<Vec<&str>> = input
+        .trim()
+        .split('\n')
+        .map(|line| line.split_ascii_whitespace().collect())
+        .collect();
+
+    let operator_index = maths.len() - 1;
+
+    (0..maths[0].len())
+        .map(|col| {
+            let numbers = (0..operator_index).map(|row| maths[row][col].parse::<usize>().unwrap());
+            match maths[operator_index][col] {
+                "*" => numbers.product::<usize>(),
+                _ => numbers.sum(),
+            }
+        })
+        .sum()
+}
+
+pub fn cephalopod_math(input: &str) -> usize {
+    let lines: Vec<&[u8]> = input
+        .trim_matches('\n')
+        .split('\n')
+        .map(|line| line.as_bytes())
+        .collect();
+
+    let rows = lines.len();
+    let cols = lines[0].len();
+
+    let mut total: usize = 0;
+    let mut col = cols;
+    let mut numbers = vec![];
+
+    loop {
+        col -= 1;
+        let mut row = rows;
+        let mut exponent = 0;
+        let mut number = 0;
+        let mut operator: u8 = 0;
+
+        loop {
+            row -= 1;
+            let val = lines[row][col];
+            match val {
+                b'0'..=b'9' => {
+                    number += (val - b'0') as usize * 10usize.pow(exponent);
+                    exponent += 1;
+                }
+                b'+' | b'*' => {
+                    operator = val;
+                }
+                _ => {}
+            }
+
+            if row == 0 {
+                break;
+            }
+        }
+
+        numbers.push(number);
+
+        match operator {
+            b'+' | b'*' => {
+                total += if operator == b'+' {
+                    numbers.iter().sum::<usize>()
+                } else {
+                    numbers.iter().product()
+                };
+                numbers.clear();
+                // if we're not at the end, skip the next column
+                // because there's an empty one after every operation
+                col = col.saturating_sub(1);
+            }
+            _ => {}
+        }
+
+        if col == 0 {
+            break;
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day06::parse(EXAMPLE).unwrap().part_one(), 4277556);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day06::parse(EXAMPLE).unwrap().part_two(), 3263827);
+    }
+}
@@ -0,0 +1,134 @@
+use common::{Grid, ParseError, Solution};
+use std::fmt;
+
+pub struct Day07 {
+    grid: Grid<ValWithCount>,
+}
+
+// a cell of the manifold along with how many beams have reached it
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ValWithCount {
+    pub val: u8,
+    pub count: usize,
+}
+
+impl ValWithCount {
+    pub fn new(val: u8, count: usize) -> Self {
+        Self { val, count }
+    }
+}
+
+impl fmt::Display for ValWithCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val as char)
+    }
+}
+
+impl Solution for Day07 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_with(
+            input,
+            |c| match c {
+                '.' | 'S' | '^' => Some(ValWithCount::new(c as u8, 0)),
+                _ => None,
+            },
+            "'.', the start 'S' or a splitter '^'",
+        )?;
+
+        Ok(Self { grid })
+    }
+
+    // the number of times the beam was split
+    fn part_one(&self) -> usize {
+        let mut grid = self.grid.clone();
+        propagate_beams(&mut grid)
+    }
+
+    // the number of timelines a single particle could end up in
+    fn part_two(&self) -> usize {
+        let mut grid = self.grid.clone();
+        propagate_beams(&mut grid);
+
+        grid.row(grid.rows() - 1)
+            .iter()
+            .map(|cell| cell.count)
+            .sum()
+    }
+}
+
+// send the beam down from the start, counting how many beams reach each cell
+// and returning the number of splits along the way
+pub fn propagate_beams(grid: &mut Grid<ValWithCount>) -> usize {
+    let mut splits = 0;
+
+    for col in 0isize..grid.cols() as isize {
+        if grid.get(0, col).unwrap().val == b'S' {
+            grid.set(1, col, ValWithCount::new(b'|', 1));
+            break;
+        }
+    }
+
+    for row in 1isize..grid.rows() as isize - 2 {
+        for col in 0isize..grid.cols() as isize {
+            let cell = *grid.get(row, col).unwrap();
+            if cell.val == b'|' {
+                match grid.get(row + 1, col).unwrap() {
+                    ValWithCount {
+                        val: b'^',
+                        count: _,
+                    } => {
+                        grid.set(
+                            row + 2,
+                            col - 1,
+                            ValWithCount::new(
+                                b'|',
+                                grid.get(row + 2, col - 1).unwrap().count + cell.count,
+                            ),
+                        );
+                        grid.set(
+                            row + 2,
+                            col + 1,
+                            ValWithCount::new(
+                                b'|',
+                                grid.get(row + 2, col + 1).unwrap().count + cell.count,
+                            ),
+                        );
+                        splits += 1
+                    }
+                    _ => {
+                        grid.set(
+                            row + 2,
+                            col,
+                            ValWithCount::new(
+                                b'|',
+                                grid.get(row + 2, col).unwrap().count + cell.count,
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    splits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day07::parse(EXAMPLE).unwrap().part_one(), 21);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day07::parse(EXAMPLE).unwrap().part_two(), 40);
+    }
+}
@@ -0,0 +1,192 @@
+use common::parse::parse_token;
+use common::{ParseError, Solution};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+// the number of pairs of boxes the real puzzle asks us to connect for part 1
+pub const DEFAULT_PAIRS: usize = 1000;
+
+pub struct Day08 {
+    boxes: Vec<Point>,
+    pairs: usize,
+}
+
+impl Day08 {
+    // the example only connects 10 pairs, so allow overriding the default
+    pub fn with_pairs(self, pairs: usize) -> Self {
+        Self { pairs, ..self }
+    }
+}
+
+impl Solution for Day08 {
+    type PartOne = usize;
+    type PartTwo = isize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let boxes = input
+            .trim()
+            .split('\n')
+            .map(|line| {
+                let coords = line
+                    .split(',')
+                    .map(|coord| parse_token(input, coord, "an integer"))
+                    .collect::<Result<Vec<isize>, _>>()?;
+                match coords[..] {
+                    [x, y, z] => Ok(Point::new(x, y, z)),
+                    _ => Err(ParseError::at(
+                        input,
+                        line,
+                        "expected three coordinates like 162,817,812",
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // part 1 multiplies the three largest circuits
+        if boxes.len() < 3 {
+            return Err(ParseError::at(
+                input,
+                input.trim(),
+                "expected at least three junction boxes",
+            ));
+        }
+
+        Ok(Self {
+            boxes,
+            pairs: DEFAULT_PAIRS,
+        })
+    }
+
+    // the product of the sizes of the three largest circuits
+    fn part_one(&self) -> usize {
+        let (circuit_sizes, _) = connect_boxes(&self.boxes, self.pairs);
+        // boxes left unconnected are circuits of one, which don't change the product
+        circuit_sizes.iter().take(3).product()
+    }
+
+    // the product of the x coordinates of the pair that completes a single circuit
+    fn part_two(&self) -> isize {
+        let (_, last_pair) = connect_boxes(&self.boxes, usize::MAX);
+        let (a, b) = last_pair.expect("parse keeps at least three boxes, so some pair connects");
+        a.x * b.x
+    }
+}
+
+// connect up to max_pairs of the closest boxes, returning the circuit sizes from
+// largest to smallest and the last pair connected, if any were
+fn connect_boxes(boxes: &[Point], max_pairs: usize) -> (Vec<usize>, Option<(Point, Point)>) {
+    let mut combinations: BinaryHeap<PointPair> = BinaryHeap::new();
+
+    for i in 0..boxes.len() {
+        for j in (i + 1)..boxes.len() {
+            combinations.push(PointPair::new(
+                &boxes[i],
+                &boxes[j],
+                boxes[i].distance_to(&boxes[j]),
+            ));
+            if combinations.len() > max_pairs {
+                combinations.pop();
+            }
+        }
+    }
+
+    let combos = combinations.into_sorted_vec();
+    let mut circuits: Vec<HashSet<&Point>> = vec![];
+    let mut last_pair: Option<&PointPair> = combos.first();
+
+    for pair in &combos[..] {
+        let mut new_circuits: Vec<HashSet<&Point>> = vec![];
+        let mut new_circuit: HashSet<&Point> = HashSet::from([pair.a, pair.b]);
+        for i in 0..circuits.len() {
+            if circuits[i].is_disjoint(&new_circuit) {
+                new_circuits.push(circuits[i].clone());
+            } else {
+                for point in &circuits[i] {
+                    new_circuit.insert(*point);
+                }
+            }
+        }
+        new_circuits.push(new_circuit);
+        circuits = new_circuits;
+
+        if circuits.len() == 1 && circuits[0].len() == boxes.len() {
+            last_pair = Some(pair);
+            break;
+        }
+    }
+
+    circuits.sort_unstable_by_key(|circuit| Reverse(circuit.len()));
+
+    (
+        circuits.iter().map(|circuit| circuit.len()).collect(),
+        last_pair.map(|pair| (*pair.a, *pair.b)),
+    )
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct Point {
+    x: isize,
+    y: isize,
+    z: isize,
+}
+
+impl Point {
+    fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    fn distance_to(&self, other: &Point) -> usize {
+        (((self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)) as f64)
+            .sqrt() as usize
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct PointPair<'a> {
+    a: &'a Point,
+    b: &'a Point,
+    dist: usize,
+}
+
+impl<'a> PointPair<'a> {
+    fn new(a: &'a Point, b: &'a Point, dist: usize) -> Self {
+        Self { a, b, dist }
+    }
+}
+
+impl<'a> Ord for PointPair<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist.cmp(&other.dist)
+    }
+}
+
+impl<'a> PartialOrd for PointPair<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day08::parse(EXAMPLE).unwrap().with_pairs(10).part_one(), 40);
+        // with nothing connected every box is a circuit of one
+        assert_eq!(Day08::parse(EXAMPLE).unwrap().with_pairs(0).part_one(), 1);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day08::parse(EXAMPLE).unwrap().part_two(), 25272);
+    }
+
+    #[test]
+    fn too_few_boxes() {
+        let err = Day08::parse("1,2,3\n4,5,6").err().unwrap();
+        assert_eq!(err.message, "expected at least three junction boxes");
+    }
+}
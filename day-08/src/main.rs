@@ -2,126 +2,25 @@
 // https://adventofcode.com/2025/day/8
 // Usage: `cargo run <input-file> [<boxes-to-connect-for-part-1>]
 
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashSet};
+use common::solution::{parse_or_exit, Solution};
+use day_08::Day08;
 use std::{env, fs};
 
 fn main() {
     let input_filename = env::args().nth(1).expect("please supply an input filename");
-    let max_pairs = if env::args().len() > 2 {
-        env::args()
-            .nth(2)
-            .expect("please specify the number of boxes to connet")
+    let max_pairs: Option<usize> = env::args().nth(2).map(|pairs| {
+        pairs
             .parse()
             .expect("please specify the number of boxes as an integer")
-    } else {
-        usize::MAX
-    };
-    let input = fs::read_to_string(input_filename).expect("failed to read input");
-
-    let boxes: Vec<Point> = input
-        .trim()
-        .split('\n')
-        .map(|line| {
-            let mut coords = line.split(',').map(|coord| coord.parse::<isize>().unwrap());
-            Point::new(
-                coords.next().unwrap(),
-                coords.next().unwrap(),
-                coords.next().unwrap(),
-            )
-        })
-        .collect();
-
-    let mut combinations: BinaryHeap<PointPair> = BinaryHeap::new();
-
-    for i in 0..boxes.len() {
-        for j in (i + 1)..boxes.len() {
-            combinations.push(PointPair::new(
-                &boxes[i],
-                &boxes[j],
-                boxes[i].distance_to(&boxes[j]),
-            ));
-            if combinations.len() > max_pairs {
-                combinations.pop();
-            }
-        }
-    }
-
-    let combos = combinations.into_sorted_vec();
-    let mut circuits: Vec<HashSet<&Point>> = vec![];
-    let mut last_pair: &PointPair = &combos[0];
-
-    for pair in &combos[..] {
-        let mut new_circuits: Vec<HashSet<&Point>> = vec![];
-        let mut new_circuit: HashSet<&Point> = HashSet::from([pair.a, pair.b]);
-        for i in 0..circuits.len() {
-            if circuits[i].is_disjoint(&new_circuit) {
-                new_circuits.push(circuits[i].clone());
-            } else {
-                for point in &circuits[i] {
-                    new_circuit.insert(point.clone());
-                }
-            }
-        }
-        new_circuits.push(new_circuit);
-        circuits = new_circuits;
+    });
+    let input = fs::read_to_string(&input_filename).expect("failed to read input");
+    let day: Day08 = parse_or_exit(&input, &input_filename);
 
-        if circuits.len() == 1 && circuits[0].len() == boxes.len() {
-            last_pair = &pair;
-            break;
-        }
-    }
-
-    circuits.sort_unstable_by_key(|circuit| Reverse(circuit.len()));
-
-    if max_pairs != usize::MAX {
-        let part_1: usize = circuits[..3].iter().map(|circuit| circuit.len()).product();
+    if let Some(max_pairs) = max_pairs {
+        let part_1 = day.with_pairs(max_pairs).part_one();
         println!("The sum of the three largest circuits was {part_1}");
     } else {
-        let part_2: isize = last_pair.a.x * last_pair.b.x;
+        let part_2 = day.part_two();
         println!("The sum of the x coordinates of the last pair was {part_2}");
     }
 }
-
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
-struct Point {
-    x: isize,
-    y: isize,
-    z: isize,
-}
-
-impl Point {
-    fn new(x: isize, y: isize, z: isize) -> Self {
-        Self { x, y, z }
-    }
-
-    fn distance_to(&self, other: &Point) -> usize {
-        (((self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)) as f64)
-            .sqrt() as usize
-    }
-}
-
-#[derive(Eq, PartialEq, Debug)]
-struct PointPair<'a> {
-    a: &'a Point,
-    b: &'a Point,
-    dist: usize,
-}
-
-impl<'a> PointPair<'a> {
-    fn new(a: &'a Point, b: &'a Point, dist: usize) -> Self {
-        Self { a, b, dist }
-    }
-}
-
-impl<'a> Ord for PointPair<'a> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.dist.cmp(&other.dist)
-    }
-}
-
-impl<'a> PartialOrd for PointPair<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(&other))
-    }
-}
@@ -0,0 +1,177 @@
+use common::parse::parse_token;
+use common::{ParseError, Solution};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+pub struct Day09 {
+    points: Vec<Point>,
+}
+
+impl Solution for Day09 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let points = input
+            .trim()
+            .split('\n')
+            .map(|line| {
+                line.parse()
+                    .map_err(|err: ParseError| err.relative_to(input, line))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // a rectangle needs two corners
+        if points.len() < 2 {
+            return Err(ParseError::at(
+                input,
+                input.trim(),
+                "expected at least two red tiles",
+            ));
+        }
+
+        Ok(Self { points })
+    }
+
+    // the largest area of a rectangle between any two red tiles
+    fn part_one(&self) -> usize {
+        let points = &self.points;
+        let mut part_1: usize = 0;
+
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                let area = Rect::new(&points[i], &points[j]).area();
+                if area > part_1 {
+                    part_1 = area;
+                }
+            }
+        }
+
+        part_1
+    }
+
+    // the largest area of a rectangle enclosed by the loop
+    fn part_two(&self) -> usize {
+        let points = &self.points;
+        let mut lines = vec![];
+
+        for i in 0..points.len() - 2 {
+            lines.push(Line::new(&points[i], &points[i + 1]));
+        }
+        lines.push(Line::new(&points[points.len() - 1], &points[0]));
+
+        let mut part_2: usize = 0;
+
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                let rect = Rect::new(&points[i], &points[j]);
+                let area = rect.area();
+                if area > part_2 && !lines.iter().any(|line| rect.is_broken_by(line)) {
+                    part_2 = area;
+                }
+            }
+        }
+
+        part_2
+    }
+}
+
+pub struct Point {
+    x: isize,
+    y: isize,
+}
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x_str, y_str) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::at(s, s, "expected a point like 7,1"))?;
+
+        let x = parse_token(s, x_str, "an integer")?;
+        let y = parse_token(s, y_str, "an integer")?;
+
+        Ok(Self { x, y })
+    }
+}
+
+struct Rect {
+    range_x: RangeInclusive<isize>,
+    range_y: RangeInclusive<isize>,
+    interior_range_x: RangeInclusive<isize>,
+    interior_range_y: RangeInclusive<isize>,
+}
+
+impl Rect {
+    fn new(corner_a: &Point, corner_b: &Point) -> Self {
+        let range_x = corner_a.x.min(corner_b.x)..=corner_a.x.max(corner_b.x);
+        let range_y = corner_a.y.min(corner_b.y)..=corner_a.y.max(corner_a.y);
+        let interior_range_x = (range_x.start() + 1)..=(range_x.end() - 1);
+        let interior_range_y = (range_y.start() + 1)..=(range_y.end() - 1);
+        Self {
+            range_x,
+            range_y,
+            interior_range_x,
+            interior_range_y,
+        }
+    }
+
+    fn area(&self) -> usize {
+        ((self.range_x.end() - self.range_x.start() + 1).abs()
+            * (self.range_y.end() - self.range_y.start() + 1).abs()) as usize
+    }
+
+    // does the given line go into the interior of the rect at all?
+    fn is_broken_by(&self, line: &Line) -> bool {
+        if line.point_a.x == line.point_b.x {
+            let min_y = line.point_a.y.min(line.point_b.y);
+            let max_y = line.point_a.y.max(line.point_b.y);
+
+            self.interior_range_x.contains(&line.point_a.x)
+                && *self.interior_range_y.start() <= max_y
+                && min_y <= *self.interior_range_y.end()
+        } else {
+            let min_x = line.point_a.x.min(line.point_b.x);
+            let max_x = line.point_a.x.max(line.point_b.x);
+
+            self.interior_range_y.contains(&line.point_a.y)
+                && *self.interior_range_x.start() <= max_x
+                && min_x <= *self.interior_range_x.end()
+        }
+    }
+}
+
+struct Line<'a> {
+    point_a: &'a Point,
+    point_b: &'a Point,
+}
+
+impl<'a> Line<'a> {
+    fn new(point_a: &'a Point, point_b: &'a Point) -> Self {
+        Self { point_a, point_b }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(Day09::parse(EXAMPLE).unwrap().part_one(), 50);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(Day09::parse(EXAMPLE).unwrap().part_two(), 24);
+    }
+
+    #[test]
+    fn too_few_tiles() {
+        let err = Day09::parse("7,1").err().unwrap();
+        assert_eq!(err.message, "expected at least two red tiles");
+    }
+}
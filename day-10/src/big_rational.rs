@@ -0,0 +1,286 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::cmp::{Ord, Ordering, PartialOrd};
+use std::convert::{From, TryFrom};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::rational::{ConversionError, Rational};
+
+// a rational backed by arbitrary precision integers, so that long chains of
+// elimination steps can't overflow the way an isize numerator can
+#[derive(Eq, PartialEq, Clone, Hash)]
+pub struct BigRational {
+    pub numerator: BigInt,
+    pub denominator: BigInt,
+}
+
+impl From<isize> for BigRational {
+    fn from(num: isize) -> Self {
+        Self::from(BigInt::from(num))
+    }
+}
+
+impl From<BigInt> for BigRational {
+    fn from(num: BigInt) -> Self {
+        Self {
+            numerator: num,
+            denominator: BigInt::one(),
+        }
+    }
+}
+
+impl From<Rational> for BigRational {
+    fn from(value: Rational) -> Self {
+        Self::new(value.numerator.into(), value.denominator.into())
+    }
+}
+
+impl TryFrom<BigRational> for isize {
+    type Error = ConversionError;
+    fn try_from(value: BigRational) -> Result<Self, Self::Error> {
+        if value.denominator.is_one() {
+            value.numerator.to_isize().ok_or(ConversionError)
+        } else {
+            Err(ConversionError)
+        }
+    }
+}
+
+impl TryFrom<BigRational> for Rational {
+    type Error = ConversionError;
+    fn try_from(value: BigRational) -> Result<Self, Self::Error> {
+        match (value.numerator.to_isize(), value.denominator.to_isize()) {
+            (Some(numerator), Some(denominator)) => Ok(Rational::new(numerator, denominator)),
+            _ => Err(ConversionError),
+        }
+    }
+}
+
+impl fmt::Display for BigRational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator.is_one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl fmt::Debug for BigRational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl BigRational {
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Self {
+        assert!(!denominator.is_zero());
+
+        if numerator.is_zero() {
+            return Self::from(0);
+        }
+
+        let gcd = numerator.gcd(&denominator);
+        let mut numerator = numerator / &gcd;
+        let mut denominator = denominator / &gcd;
+        if denominator.is_negative() {
+            numerator = -numerator;
+            denominator = -denominator;
+        }
+
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    pub fn floor(&self) -> BigInt {
+        self.numerator.div_floor(&self.denominator)
+    }
+
+    pub fn ceil(&self) -> BigInt {
+        -(-&self.numerator).div_floor(&self.denominator)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    pub fn is_non_negative_integer(&self) -> bool {
+        self.denominator.is_one() && !self.numerator.is_negative()
+    }
+}
+
+impl Ord for BigRational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for BigRational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(
+            &self.numerator * &other.denominator + &self.denominator * &other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl AddAssign for BigRational {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.clone() + rhs;
+    }
+}
+
+impl Sub for BigRational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(
+            &self.numerator * &other.denominator - &self.denominator * &other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl SubAssign for BigRational {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.clone() - rhs;
+    }
+}
+
+impl Mul for BigRational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl MulAssign for BigRational {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl Div for BigRational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+impl DivAssign for BigRational {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.clone() / rhs;
+    }
+}
+
+impl Neg for BigRational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Sum for BigRational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|result, num| result + num).unwrap_or(0.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::Scalar;
+
+    fn big(numerator: isize, denominator: isize) -> BigRational {
+        BigRational::new(numerator.into(), denominator.into())
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(big(0, -200), big(0, 1));
+        assert_eq!(big(100, 200), big(1, 2));
+        assert_eq!(big(10, -15), big(-2, 3));
+        assert_eq!(big(-4, -16), big(1, 4));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(big(1, 3) + big(1, 4), big(7, 12));
+        assert_eq!(big(1, 3) - big(4, 3), big(-1, 1));
+        assert_eq!(big(1, 2) * big(1, 2), big(1, 4));
+        assert_eq!(big(1, 2) / big(1, 2), 1.into());
+        assert_eq!(-big(-7, 3), big(7, 3));
+    }
+
+    #[test]
+    fn no_overflow() {
+        // this product overflows an isize numerator but is exact here
+        let large = BigRational::from(isize::MAX);
+        let squared = large.clone() * large.clone();
+        assert_eq!(squared.clone() / large.clone(), large);
+        assert!(isize::try_from(squared).is_err());
+    }
+
+    #[test]
+    fn cmp() {
+        assert!(big(1, 2) > big(1, 3));
+        assert!(big(-1, 2) < big(-1, 3));
+    }
+
+    #[test]
+    fn floor_and_ceil() {
+        assert_eq!(big(1, 3).floor(), 0.into());
+        assert_eq!(big(1, 3).ceil(), 1.into());
+        assert_eq!(big(-1, 3).floor(), (-1).into());
+        assert_eq!(big(-1, 3).ceil(), 0.into());
+        assert_eq!(big(4, 2).ceil(), 2.into());
+
+        // the scalar versions stay exact well beyond isize
+        let beyond = BigRational::from(isize::MAX) * 3.into() + big(1, 2);
+        assert_eq!(
+            Scalar::floor(&beyond),
+            BigRational::from(isize::MAX) * 3.into()
+        );
+        assert_eq!(Scalar::ceil(&beyond), Scalar::floor(&beyond) + 1.into());
+        assert!(!beyond.is_integer());
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(BigRational::from(Rational::new(-3, 4)), big(-3, 4));
+        assert_eq!(Rational::try_from(big(6, 8)).unwrap(), Rational::new(3, 4));
+        assert_eq!(isize::try_from(big(6, 3)).unwrap(), 2);
+    }
+}
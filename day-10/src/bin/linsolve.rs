@@ -0,0 +1,112 @@
+// Solve a system of linear equations written one per line, like `2a + b - c = 5`
+// Usage: `cargo run -p day-10 --bin linsolve -- [--min-integer] [<input-file>]`
+//
+// Reads the system from `<input-file>`, or stdin when it's missing or `-`, and
+// prints its reduced row echelon form, the pivot and free columns, and the
+// parametric solution. With `--min-integer` it also prints the non-negative
+// integer solution with the smallest sum of the variables, if there is one, or says
+// it's unknown and exits with 1 when the search has to give up.
+
+use common::ParseError;
+use day_10::big_rational::BigRational;
+use day_10::equations::parse_system;
+use day_10::ilp::minimise_integer;
+use day_10::linear_algebra::{
+    extract_parametric_solution, extract_pivots, gauss_jordan_to_rref, LinearSystem,
+};
+use std::io::{self, Read};
+use std::{env, fs, process};
+
+fn main() {
+    let mut min_integer = false;
+    let mut input_filename: Option<String> = None;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--min-integer" => min_integer = true,
+            _ if arg.starts_with("--") || input_filename.is_some() => usage(),
+            _ => input_filename = Some(arg),
+        }
+    }
+
+    let input = match input_filename.as_deref() {
+        None | Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .expect("failed to read stdin");
+            input
+        }
+        Some(filename) => fs::read_to_string(filename).expect("failed to read input"),
+    };
+
+    let (system, names): (LinearSystem<BigRational>, _) =
+        parse_system(&input).unwrap_or_else(|err: ParseError| {
+            let err = match &input_filename {
+                Some(filename) if filename != "-" => err.with_file(filename),
+                _ => err,
+            };
+            eprintln!("{err}");
+            process::exit(1);
+        });
+
+    let header = (0..system.variables())
+        .map(|i| names.name(i))
+        .collect::<Vec<String>>()
+        .join(", ");
+    println!("variables: {header}");
+    println!("\nsystem:\n{system}");
+
+    let mut rref = system.clone();
+    let result = gauss_jordan_to_rref(&mut rref);
+    println!("rref:\n{rref}");
+
+    let rank = match result {
+        Ok(rank) => rank,
+        Err(err) => {
+            println!("{err}");
+            process::exit(1);
+        }
+    };
+
+    let pivot_data = extract_pivots(&rref);
+    let mut pivot_columns: Vec<usize> = pivot_data.pivot_row_for_col.keys().copied().collect();
+    pivot_columns.sort_unstable();
+    let column_names = |cols: &[usize]| {
+        cols.iter()
+            .map(|col| names.name(*col))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    println!("rank: {rank}");
+    println!("pivot columns: {}", column_names(&pivot_columns));
+    println!("free columns: {}", column_names(&pivot_data.free_columns));
+
+    println!("\nparametric solution:");
+    for expression in extract_parametric_solution(&rref, &pivot_data) {
+        println!("{}", expression.named(&names));
+    }
+
+    if min_integer {
+        rref.truncate(rank);
+        let ones = vec![BigRational::from(1); rref.variables()];
+        println!("\nminimum non-negative integer solution:");
+        match minimise_integer(&rref, &ones) {
+            Ok(Some((values, total))) => {
+                for (i, value) in values.iter().enumerate() {
+                    println!("{} = {value}", names.name(i));
+                }
+                println!("total = {total}");
+            }
+            Ok(None) => println!("none"),
+            Err(err) => {
+                println!("unknown, {err}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: linsolve [--min-integer] [<input-file>]");
+    process::exit(2);
+}
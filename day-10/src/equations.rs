@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use common::parse::parse_token;
+use common::ParseError;
+
+use crate::linear_algebra::{AffineExpression, Dependent, LinearSystem, VariableNames};
+use crate::scalar::Scalar;
+
+// parse a system of linear equations like `2a + b - c = 5`, one per line, into its
+// augmented matrix. variables can appear on either side and are numbered in the order
+// they first appear, which the returned labels record
+pub fn parse_system<T: Scalar>(
+    input: &str,
+) -> Result<(LinearSystem<T>, VariableNames), ParseError> {
+    let mut labels: Vec<&str> = vec![];
+    let mut equations: Vec<(HashMap<usize, T>, T)> = vec![];
+
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let (lhs, rhs) = split_equation(input, line)?;
+
+        // move the variables to the left and the constants to the right
+        let mut coefficients: HashMap<usize, T> = HashMap::new();
+        let mut constant = T::zero();
+        for (side, sign) in [(lhs, T::one()), (rhs, -T::one())] {
+            for (name, coefficient) in parse_terms::<T>(input, side)? {
+                let coefficient = sign.clone() * coefficient;
+                match name {
+                    Some(name) => {
+                        let index = labels.iter().position(|label| *label == name);
+                        let index = index.unwrap_or_else(|| {
+                            labels.push(name);
+                            labels.len() - 1
+                        });
+                        *coefficients.entry(index).or_insert_with(T::zero) += coefficient;
+                    }
+                    None => constant -= coefficient,
+                }
+            }
+        }
+        equations.push((coefficients, constant));
+    }
+
+    if equations.is_empty() {
+        return Err(ParseError::at(
+            input,
+            input.trim(),
+            "expected at least one equation",
+        ));
+    }
+
+    let rows = equations
+        .into_iter()
+        .map(|(coefficients, constant)| {
+            let mut row = vec![T::zero(); labels.len() + 1];
+            for (index, coefficient) in coefficients {
+                row[index] = coefficient;
+            }
+            row[labels.len()] = constant;
+            row
+        })
+        .collect();
+    let names = VariableNames::Labels(labels.into_iter().map(String::from).collect());
+
+    Ok((LinearSystem::from_augmented(rows), names))
+}
+
+// parses what Display prints, like `x0 = -42 + 2x2` or `total = 3 - x1`
+impl<T: Scalar> FromStr for AffineExpression<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (lhs, rhs) = split_equation(s, s)?;
+
+        let dependent = lhs.trim();
+        let dependent_variable = match dependent {
+            "total" => Dependent::Total,
+            _ => Dependent::Variable(indexed_variable(s, dependent)?),
+        };
+
+        let mut constant = T::zero();
+        let mut free_variable_coefficients: HashMap<usize, T> = HashMap::new();
+        for (name, coefficient) in parse_terms::<T>(s, rhs)? {
+            match name {
+                Some(name) => {
+                    *free_variable_coefficients
+                        .entry(indexed_variable(s, name)?)
+                        .or_insert_with(T::zero) += coefficient;
+                }
+                None => constant += coefficient,
+            }
+        }
+
+        Ok(Self {
+            dependent_variable,
+            constant,
+            free_variable_coefficients,
+        })
+    }
+}
+
+fn split_equation<'a>(input: &str, s: &'a str) -> Result<(&'a str, &'a str), ParseError> {
+    let mut equals = s.match_indices('=').map(|(i, _)| i);
+    match (equals.next(), equals.next()) {
+        (Some(i), None) => Ok((&s[..i], &s[i + 1..])),
+        (Some(_), Some(i)) => Err(ParseError::at(input, &s[i..=i], "expected a single '='")),
+        _ => Err(ParseError::at(
+            input,
+            s,
+            "expected an equation like 2a + b - c = 5",
+        )),
+    }
+}
+
+// the index from a variable named like x12
+fn indexed_variable(input: &str, name: &str) -> Result<usize, ParseError> {
+    match name.strip_prefix('x') {
+        Some(index) if !index.is_empty() => parse_token(input, index, "a variable index"),
+        _ => Err(ParseError::at(input, name, "expected a variable like x0")),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Number(&'a str),
+    Name(&'a str),
+    Plus,
+    Minus,
+    Slash,
+    Star,
+}
+
+// split into tokens, each paired with its slice of s for error positions
+fn tokenize<'a>(input: &str, s: &'a str) -> Result<Vec<(Token<'a>, &'a str)>, ParseError> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end_of = |matches: fn(char) -> bool| {
+            let mut end = start + c.len_utf8();
+            while let Some((i, c)) = chars.next_if(|(_, c)| matches(*c)) {
+                end = i + c.len_utf8();
+            }
+            &s[start..end]
+        };
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '/' => Token::Slash,
+            '*' => Token::Star,
+            _ if c.is_ascii_digit() => Token::Number(end_of(|c| c.is_ascii_digit())),
+            _ if c.is_alphabetic() || c == '_' => {
+                Token::Name(end_of(|c| c.is_alphanumeric() || c == '_'))
+            }
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    &s[start..start + c.len_utf8()],
+                    "expected a number, variable or one of + - / *",
+                ))
+            }
+        };
+        let text = match token {
+            Token::Number(text) | Token::Name(text) => text,
+            _ => &s[start..start + c.len_utf8()],
+        };
+        tokens.push((token, text));
+    }
+
+    Ok(tokens)
+}
+
+// parse a sum of terms like `-2a + 3/4 b - c + 5`, returning each term's variable
+// name (None for constants) and coefficient
+fn parse_terms<'a, T: Scalar>(
+    input: &str,
+    s: &'a str,
+) -> Result<Vec<(Option<&'a str>, T)>, ParseError> {
+    let tokens = tokenize(input, s)?;
+    let nothing = &s[s.len()..];
+    let mut terms = vec![];
+    let mut i = 0;
+
+    if tokens.is_empty() {
+        return Err(ParseError::at(
+            input,
+            s.trim(),
+            "expected at least one term",
+        ));
+    }
+
+    while i < tokens.len() {
+        let mut coefficient = T::one();
+
+        // a leading sign is optional on the first term only
+        match tokens[i].0 {
+            Token::Plus => i += 1,
+            Token::Minus => {
+                coefficient = -coefficient;
+                i += 1;
+            }
+            _ if terms.is_empty() => {}
+            _ => return Err(ParseError::at(input, tokens[i].1, "expected + or -")),
+        }
+
+        let token_at = |i: usize| {
+            tokens
+                .get(i)
+                .map_or((None, nothing), |(t, s)| (Some(*t), *s))
+        };
+
+        let mut has_number = false;
+        if let (Some(Token::Number(numerator)), _) = token_at(i) {
+            coefficient *= T::from(parse_token::<isize>(input, numerator, "an integer")?);
+            has_number = true;
+            i += 1;
+            if let (Some(Token::Slash), _) = token_at(i) {
+                match token_at(i + 1) {
+                    (Some(Token::Number(denominator)), _) => {
+                        let denominator: isize = parse_token(input, denominator, "an integer")?;
+                        if denominator == 0 {
+                            return Err(ParseError::at(
+                                input,
+                                tokens[i + 1].1,
+                                "expected a non-zero denominator",
+                            ));
+                        }
+                        coefficient /= T::from(denominator);
+                        i += 2;
+                    }
+                    (_, found) => {
+                        return Err(ParseError::at(input, found, "expected a denominator"))
+                    }
+                }
+            }
+            if let (Some(Token::Star), _) = token_at(i) {
+                i += 1;
+                if !matches!(token_at(i).0, Some(Token::Name(_))) {
+                    return Err(ParseError::at(input, token_at(i).1, "expected a variable"));
+                }
+            }
+        }
+
+        match token_at(i) {
+            (Some(Token::Name(name)), _) => {
+                terms.push((Some(name), coefficient));
+                i += 1;
+            }
+            _ if has_number => terms.push((None, coefficient)),
+            (_, found) => {
+                return Err(ParseError::at(
+                    input,
+                    found,
+                    "expected a number or variable",
+                ))
+            }
+        }
+    }
+
+    Ok(terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::big_rational::BigRational;
+    use crate::ilp::{minimise_integer, IntegerProgramError};
+    use crate::linear_algebra::gauss_jordan_to_rref;
+    use crate::rational::Rational;
+
+    #[test]
+    fn parse_affine_expression_round_trip() {
+        let expression: AffineExpression = "x0 = -42 + 2x2 - 3/4x5".parse().unwrap();
+
+        assert_eq!(expression.dependent_variable, Dependent::Variable(0));
+        assert_eq!(expression.constant, Rational::from(-42));
+        assert_eq!(expression.free_variable_coefficients[&2], Rational::from(2));
+        assert_eq!(
+            expression.free_variable_coefficients[&5],
+            Rational::new(-3, 4)
+        );
+        assert_eq!(format!("{expression}"), "x0 = -42 + 2x2 - 3/4x5");
+
+        let total: AffineExpression = "total = 7".parse().unwrap();
+        assert_eq!(total.dependent_variable, Dependent::Total);
+    }
+
+    #[test]
+    fn parse_system_of_equations() {
+        let input = "2a + b - c = 5\nb = 3 - 2c + a\n\n4 = c";
+        let (system, names) = parse_system::<Rational>(input).unwrap();
+
+        let rationals = |row: &[isize]| row.iter().map(|v| Rational::from(*v)).collect();
+        assert_eq!(
+            system,
+            LinearSystem::from_augmented(vec![
+                rationals(&[2, 1, -1, 5]),
+                rationals(&[-1, 1, 2, 3]),
+                rationals(&[0, 0, -1, -4]),
+            ])
+        );
+        assert_eq!(names.name(2), "c");
+    }
+
+    #[test]
+    fn parse_errors_have_positions() {
+        let err = parse_system::<Rational>("a + b = 1\na + = 2").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+
+        let err = parse_system::<Rational>("a + b = 1 = 2").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 11));
+
+        let err = "y = 2x1".parse::<AffineExpression>().unwrap_err();
+        assert_eq!(err.message, "expected a variable like x0");
+    }
+
+    #[test]
+    fn min_integer_solution_of_a_parsed_system() {
+        // what linsolve --min-integer does with a system from the command line
+        let min_integer = |input: &str| {
+            let (mut system, _) = parse_system::<BigRational>(input).unwrap();
+            let rank = gauss_jordan_to_rref(&mut system).unwrap();
+            system.truncate(rank);
+            let ones = vec![BigRational::from(1); system.variables()];
+            minimise_integer(&system, &ones).map(|best| best.map(|(_, total)| total))
+        };
+
+        assert_eq!(min_integer("a + b = 3\nb - c = 1"), Ok(Some(3.into())));
+        assert_eq!(min_integer("2a + 2b = 3"), Ok(None));
+        // a and b can grow together forever without ever differing by a half
+        assert_eq!(
+            min_integer("2a - 2b = 1"),
+            Err(IntegerProgramError::NodeLimit)
+        );
+    }
+}
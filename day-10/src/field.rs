@@ -0,0 +1,90 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::big_rational::BigRational;
+use crate::rational::Rational;
+
+// the numbers gaussian elimination can be run over
+pub trait Field:
+    Clone
+    + PartialEq
+    + fmt::Display
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    // exact fields compare exactly, but floats count anything within a
+    // tolerance of zero so that rounding error doesn't create false pivots
+    fn is_zero(&self) -> bool;
+
+    fn is_one(&self) -> bool {
+        (self.clone() - Self::one()).is_zero()
+    }
+
+    // when several rows could supply a pivot, whether this value is a better
+    // pivot than other; by default a 1 is preferred since it needs no normalizing
+    fn is_better_pivot_than(&self, other: &Self) -> bool {
+        self.is_one() && !other.is_one()
+    }
+}
+
+impl Field for Rational {
+    fn zero() -> Self {
+        0.into()
+    }
+
+    fn one() -> Self {
+        1.into()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+}
+
+impl Field for BigRational {
+    fn zero() -> Self {
+        0.into()
+    }
+
+    fn one() -> Self {
+        1.into()
+    }
+
+    fn is_zero(&self) -> bool {
+        BigRational::is_zero(self)
+    }
+}
+
+// values closer to zero than this are treated as zero when eliminating over floats
+pub const F64_TOLERANCE: f64 = 1e-9;
+
+impl Field for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn is_zero(&self) -> bool {
+        self.abs() < F64_TOLERANCE
+    }
+
+    // partial pivoting: dividing by the largest magnitude keeps rounding error down
+    fn is_better_pivot_than(&self, other: &Self) -> bool {
+        self.abs() > other.abs()
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt;
+
+use crate::linear_algebra::{minimise, Constraint, LinearProgram, LinearSystem, Relation};
+use crate::scalar::Scalar;
+
+// when some variable has no upper bound nothing else stops the branching, so give up
+// after solving this many relaxations
+pub const MAX_UNBOUNDED_NODES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerProgramError {
+    // the relaxation's objective has no lower bound
+    Unbounded,
+    // the search hit MAX_UNBOUNDED_NODES without settling
+    NodeLimit,
+}
+
+impl fmt::Display for IntegerProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegerProgramError::Unbounded => write!(f, "the objective has no lower bound"),
+            IntegerProgramError::NodeLimit => write!(
+                f,
+                "gave up after {MAX_UNBOUNDED_NODES} relaxations, as some variable has no upper bound"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IntegerProgramError {}
+
+// the tightest bounds on each variable found while branching, beyond x >= 0
+#[derive(Debug, Clone)]
+struct Bounds<T> {
+    at_least: Vec<Option<T>>,
+    at_most: Vec<Option<T>>,
+}
+
+// minimise objective . x subject to the augmented equations [A | b], meaning A x = b,
+// with every x a non-negative integer. returns the best values and objective, or
+// None when no integer solution exists.
+//
+// every branch rules out the fractional values between two integers, so when each
+// variable has an upper bound the search must end. those bounds come from maximising
+// each variable over the relaxation, and if any is unbounded the search is cut off at
+// MAX_UNBOUNDED_NODES with an error instead
+pub fn minimise_integer<T: Scalar>(
+    equations: &LinearSystem<T>,
+    objective: &[T],
+) -> Result<Option<(Vec<T>, T)>, IntegerProgramError> {
+    let vars = objective.len();
+    let mut root = Bounds {
+        at_least: vec![None; vars],
+        at_most: vec![None; vars],
+    };
+    for var in 0..vars {
+        let mut maximise = vec![T::zero(); vars];
+        maximise[var] = -T::one();
+        match relax(equations, &maximise, &root) {
+            LinearProgram::Optimal { objective, .. } => {
+                root.at_most[var] = Some((-objective).floor())
+            }
+            LinearProgram::Infeasible { .. } => return Ok(None),
+            LinearProgram::Unbounded { .. } => {}
+        }
+    }
+    let bounded = root.at_most.iter().all(Option::is_some);
+
+    // with integer costs every integer solution has an integer objective, so a
+    // relaxation can be rounded up before comparing it against the best so far
+    let integer_costs = objective.iter().all(T::is_integer);
+
+    let mut best: Option<(Vec<T>, T)> = None;
+    let mut stack: Vec<Bounds<T>> = vec![root];
+    let mut nodes = 0;
+
+    while let Some(bounds) = stack.pop() {
+        nodes += 1;
+        if !bounded && nodes > MAX_UNBOUNDED_NODES {
+            return Err(IntegerProgramError::NodeLimit);
+        }
+
+        let (values, relaxed) = match relax(equations, objective, &bounds) {
+            LinearProgram::Optimal {
+                values, objective, ..
+            } => (values, objective),
+            LinearProgram::Infeasible { .. } => continue,
+            LinearProgram::Unbounded { .. } => return Err(IntegerProgramError::Unbounded),
+        };
+
+        let relaxed = if integer_costs {
+            relaxed.ceil()
+        } else {
+            relaxed
+        };
+        if matches!(&best, Some((_, objective)) if relaxed >= *objective) {
+            continue;
+        }
+
+        match values.iter().position(|value| !value.is_integer()) {
+            Some(var) => {
+                // split the relaxation either side of the fractional value, exploring
+                // the round-down side first since it tends to find small objectives
+                let value = &values[var];
+                let mut up = bounds.clone();
+                up.at_least[var] = Some(value.ceil());
+                stack.push(up);
+                let mut down = bounds;
+                down.at_most[var] = Some(value.floor());
+                stack.push(down);
+            }
+            None => {
+                let objective = dot(objective, &values);
+                best = Some((values, objective));
+            }
+        }
+    }
+
+    Ok(best)
+}
+
+fn dot<T: Scalar>(a: &[T], b: &[T]) -> T {
+    a.iter().zip(b).map(|(a, b)| a.clone() * b.clone()).sum()
+}
+
+// solve the linear relaxation with the branching bounds added as extra constraints
+fn relax<T: Scalar>(
+    equations: &LinearSystem<T>,
+    objective: &[T],
+    bounds: &Bounds<T>,
+) -> LinearProgram<T> {
+    let vars = objective.len();
+
+    let mut constraints: Vec<Constraint<T>> = (0..equations.equations())
+        .map(|row| {
+            let (coefficients, rhs) = equations.equation(row);
+            Constraint::new(coefficients.to_vec(), Relation::Equal, rhs.clone())
+        })
+        .collect();
+
+    let at_least = bounds
+        .at_least
+        .iter()
+        .map(|bound| (Relation::AtLeast, bound));
+    let at_most = bounds.at_most.iter().map(|bound| (Relation::AtMost, bound));
+    for (var, (relation, bound)) in at_least.enumerate().chain(at_most.enumerate()) {
+        if let Some(rhs) = bound {
+            let mut coefficients = vec![T::zero(); vars];
+            coefficients[var] = T::one();
+            constraints.push(Constraint::new(coefficients, relation, rhs.clone()));
+        }
+    }
+
+    minimise(objective, &constraints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rational::Rational;
+
+    fn to_system(rows: &[&[isize]]) -> LinearSystem {
+        LinearSystem::from_augmented(
+            rows.iter()
+                .map(|row| row.iter().map(|v| Rational::from(*v)).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn branches_past_a_fractional_relaxation() {
+        // 2a + 2b = 3 has relaxed solutions but no integer ones
+        let equations = to_system(&[&[2, 2, 3]]);
+        let objective = vec![Rational::from(1); 2];
+        assert_eq!(minimise_integer(&equations, &objective), Ok(None));
+
+        // 2a + 3b = 7 relaxes to b = 7/3, but the best integer solution is a = 2, b = 1
+        let equations = to_system(&[&[2, 3, 7]]);
+        let (values, presses) = minimise_integer(&equations, &objective).unwrap().unwrap();
+        assert_eq!(values, vec![Rational::from(2), Rational::from(1)]);
+        assert_eq!(presses, Rational::from(3));
+    }
+
+    #[test]
+    fn gives_up_without_upper_bounds() {
+        // 2a - 2b = 1 has relaxed solutions with a and b as large as you like, but
+        // no integer ones, so branching would go on forever
+        let equations = to_system(&[&[2, -2, 1]]);
+        let objective = vec![Rational::from(1); 2];
+        assert_eq!(
+            minimise_integer(&equations, &objective),
+            Err(IntegerProgramError::NodeLimit)
+        );
+    }
+}
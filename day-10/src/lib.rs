@@ -1,2 +1,68 @@
+pub mod big_rational;
+pub mod equations;
+pub mod field;
+pub mod ilp;
 pub mod linear_algebra;
+pub mod machine;
+pub mod matrix;
+pub mod prime_field;
 pub mod rational;
+pub mod scalar;
+
+use common::{ParseError, Solution};
+use machine::{fewest_presses_for_joltage, fewest_presses_for_lights, Machine, Presses};
+
+pub struct Day10 {
+    machines: Vec<Machine>,
+}
+
+impl Solution for Day10 {
+    type PartOne = Presses;
+    type PartTwo = Presses;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let machines = input
+            .trim()
+            .split('\n')
+            .map(|line| {
+                line.parse()
+                    .map_err(|err: ParseError| err.relative_to(input, line))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { machines })
+    }
+
+    // the fewest button presses to configure every machine's lights
+    fn part_one(&self) -> Presses {
+        self.machines.iter().map(fewest_presses_for_lights).sum()
+    }
+
+    // the fewest button presses to configure every machine's joltages
+    fn part_two(&self) -> Presses {
+        self.machines.iter().map(fewest_presses_for_joltage).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(
+            Day10::parse(EXAMPLE).unwrap().part_one(),
+            Presses::Fewest(7.into())
+        );
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(
+            Day10::parse(EXAMPLE).unwrap().part_two(),
+            Presses::Fewest(33.into())
+        );
+    }
+}
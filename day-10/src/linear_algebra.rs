@@ -2,105 +2,163 @@ use core::fmt;
 use std::collections::HashMap;
 use std::iter::Sum;
 
+use crate::field::Field;
+use crate::matrix::write_rows;
 use crate::rational::Rational;
+use crate::scalar::Scalar;
 
-pub fn naive_max_bounds(equations: &[Vec<Rational>]) -> HashMap<usize, Rational> {
-    let rows = equations.len();
-    let cols = equations[0].len();
-    let global_max = (0..rows).map(|row| equations[row][cols - 1]).max().unwrap();
-    let mut max_bounds: HashMap<usize, Rational> =
-        (0..(cols - 1)).map(|i| (i, global_max)).collect();
+pub use crate::matrix::Matrix;
 
-    for equation in equations.iter() {
-        for var in 0..(cols - 1) {
-            if equation[var] == 1.into() {
-                max_bounds
-                    .entry(var)
-                    .and_modify(|old_max| *old_max = *old_max.min(&mut equation[cols - 1].clone()));
-            }
+// a system of linear equations A x = b, kept as the augmented matrix [A | b] so that
+// row operations apply to both at once
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearSystem<T = Rational> {
+    augmented: Matrix<T>,
+}
+
+impl<T: Field> LinearSystem<T> {
+    pub fn new(coefficients: Matrix<T>, rhs: Vec<T>) -> Self {
+        assert_eq!(
+            coefficients.rows(),
+            rhs.len(),
+            "there must be one rhs per equation"
+        );
+        let augmented = Matrix::from_rows(
+            coefficients
+                .iter_rows()
+                .zip(rhs)
+                .map(|(row, rhs)| {
+                    let mut row = row.to_vec();
+                    row.push(rhs);
+                    row
+                })
+                .collect(),
+            coefficients.cols() + 1,
+        );
+        Self { augmented }
+    }
+
+    // rows of coefficients with the rhs as the last value of each. without any rows
+    // there is nothing to count the variables from, so that's taken as none
+    pub fn from_augmented(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(1, |row| row.len());
+        assert!(cols > 0, "every equation needs a rhs");
+        Self {
+            augmented: Matrix::from_rows(rows, cols),
         }
     }
 
-    max_bounds
-}
+    pub fn equations(&self) -> usize {
+        self.augmented.rows()
+    }
 
-pub fn gauss_jordan_to_rref(equations: &mut Vec<Vec<Rational>>) {
-    let rows = equations.len();
-    let cols = equations[0].len();
+    pub fn variables(&self) -> usize {
+        self.augmented.cols() - 1
+    }
 
-    let mut pivot_row: usize = 0;
-    let mut pivot_col_start: usize = 0;
+    pub fn coefficients(&self) -> Matrix<T> {
+        Matrix::from_rows(
+            self.augmented
+                .iter_rows()
+                .map(|row| row[..self.variables()].to_vec())
+                .collect(),
+            self.variables(),
+        )
+    }
 
-    loop {
-        if pivot_row == equations.len() {
-            break;
-        }
+    pub fn rhs(&self) -> Vec<T> {
+        self.augmented.column(self.variables())
+    }
 
-        if let Some((new_pivot_row, pivot_col)) = find_pivot(equations, pivot_row, pivot_col_start)
-        {
-            // find pivot
-            if new_pivot_row != pivot_row {
-                equations.swap(pivot_row, new_pivot_row);
-            }
-            // normalize
-            let p = equations[pivot_row][pivot_col];
-            if p != 1.into() {
-                for col in 0..cols {
-                    equations[pivot_row][col] /= p;
-                }
-            }
-            // eliminate that column everywhere else above and below
-            for row in 0..rows {
-                if row == pivot_row {
-                    continue;
-                }
+    // the coefficients and rhs of one equation
+    pub fn equation(&self, row: usize) -> (&[T], &T) {
+        let (rhs, coefficients) = self.augmented.row(row).split_last().unwrap();
+        (coefficients, rhs)
+    }
 
-                let factor = equations[row][pivot_col];
-                if factor == 0.into() {
-                    continue;
-                }
+    pub fn augmented(&self) -> &Matrix<T> {
+        &self.augmented
+    }
 
-                for col in 0..cols {
-                    let pivot_row_val = equations[pivot_row][col];
-                    equations[row][col] -= factor * pivot_row_val
-                }
-            }
-            pivot_col_start = pivot_col + 1;
-            pivot_row += 1;
-        } else {
-            break;
-        }
+    // drop every equation after the first few, like the zero rows below the rank
+    pub fn truncate(&mut self, equations: usize) {
+        self.augmented.truncate_rows(equations);
     }
+}
 
-    // sanity check assertion
-    // if we have a row something like [0 0 0 0 | 5] that's invalid/impossible
-    for row in 0..rows {
-        if (0..(cols - 1)).all(|col| equations[row][col] == 0.into())
-            && equations[row][cols - 1] != 0.into()
-        {
-            panic!("row had all zeros except a non-zero in the rhs which is impossible");
-        }
+impl<T: Field> fmt::Display for LinearSystem<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<Vec<&T>> = self
+            .augmented
+            .iter_rows()
+            .map(|row| row.iter().collect())
+            .collect();
+        write_rows(f, &rows, Some(self.variables()))
     }
 }
 
-fn find_pivot(
-    equations: &[Vec<Rational>],
-    pivot_row: usize,
-    pivot_col_start: usize,
-) -> Option<(usize, usize)> {
-    for col in pivot_col_start..equations[0].len() - 1 {
-        for row in pivot_row..equations.len() {
-            if equations[row][col] == 1.into() {
-                return Some((row, col));
+// a quick upper bound on each variable of a system with non-negative coefficients:
+// nothing exceeds the largest rhs, and a variable with coefficient one in an
+// equation can't exceed that equation's rhs. without any equations there's no bound
+pub fn naive_max_bounds<T: Scalar>(system: &LinearSystem<T>) -> HashMap<usize, T> {
+    let Some(global_max) = system.rhs().into_iter().max() else {
+        return HashMap::new();
+    };
+    let mut max_bounds: HashMap<usize, T> = (0..system.variables())
+        .map(|i| (i, global_max.clone()))
+        .collect();
+
+    for row in 0..system.equations() {
+        let (coefficients, rhs) = system.equation(row);
+        for (var, coefficient) in coefficients.iter().enumerate() {
+            if coefficient.is_one() {
+                max_bounds
+                    .entry(var)
+                    .and_modify(|old_max| *old_max = old_max.clone().min(rhs.clone()));
             }
         }
-        for row in pivot_row..equations.len() {
-            if equations[row][col] != 0.into() {
-                return Some((row, col));
+    }
+
+    max_bounds
+}
+
+// a row of the reduced matrix that came out as 0 = c for some non-zero c
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EliminationError {
+    Inconsistent { row: usize },
+}
+
+impl fmt::Display for EliminationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EliminationError::Inconsistent { row } => {
+                write!(
+                    f,
+                    "row {row} reduced to 0 = c for a non-zero c, so there is no solution"
+                )
             }
         }
     }
-    None
+}
+
+impl std::error::Error for EliminationError {}
+
+// reduce the augmented matrix in place, returning its rank (the number of pivot rows,
+// which end up first) or the first row showing the system has no solution
+pub fn gauss_jordan_to_rref<T: Field>(
+    system: &mut LinearSystem<T>,
+) -> Result<usize, EliminationError> {
+    let variables = system.variables();
+    let rank = system.augmented.row_reduce(variables).len();
+
+    // if we have a row something like [0 0 0 0 | 5] that's invalid/impossible
+    for row in rank..system.equations() {
+        if !system.augmented[(row, variables)].is_zero() {
+            return Err(EliminationError::Inconsistent { row });
+        }
+    }
+
+    Ok(rank)
 }
 
 #[derive(Debug)]
@@ -109,25 +167,20 @@ pub struct PivotData {
     pub free_columns: Vec<usize>,
 }
 
-pub fn extract_pivots(rref_matrix: &[Vec<Rational>]) -> PivotData {
-    let rows = rref_matrix.len();
-    let cols = rref_matrix[0].len();
+pub fn extract_pivots<T: Field>(rref_system: &LinearSystem<T>) -> PivotData {
     let mut free_columns = vec![];
     let mut pivot_row_for_col: HashMap<usize, usize> = HashMap::new();
 
-    'rows: for row in 0..rows {
-        for col in 0..(cols - 1) {
-            let val = rref_matrix[row][col];
-            if val != 0.into() {
-                if val == 1.into() {
-                    pivot_row_for_col.insert(col, row);
-                }
-                continue 'rows;
+    for row in 0..rref_system.equations() {
+        let (coefficients, _) = rref_system.equation(row);
+        if let Some(col) = coefficients.iter().position(|val| !val.is_zero()) {
+            if coefficients[col].is_one() {
+                pivot_row_for_col.insert(col, row);
             }
         }
     }
 
-    for col in 0..(cols - 1) {
+    for col in 0..rref_system.variables() {
         if !pivot_row_for_col.contains_key(&col) {
             free_columns.push(col);
         }
@@ -139,23 +192,56 @@ pub fn extract_pivots(rref_matrix: &[Vec<Rational>]) -> PivotData {
     }
 }
 
+// what an affine expression is equal to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dependent {
+    Variable(usize),
+    // the sum of several expressions, like the total number of presses
+    Total,
+}
+
+// how to print variables, where an index past the end of the labels falls back
+// to the indexed name like x26
+#[derive(Debug, Clone, Default)]
+pub enum VariableNames {
+    #[default]
+    Indexed,
+    Labels(Vec<String>),
+}
+
+impl VariableNames {
+    pub fn name(&self, index: usize) -> String {
+        match self {
+            VariableNames::Labels(labels) if index < labels.len() => labels[index].clone(),
+            _ => format!("x{index}"),
+        }
+    }
+
+    fn dependent(&self, dependent: Dependent) -> String {
+        match dependent {
+            Dependent::Variable(index) => self.name(index),
+            Dependent::Total => "total".into(),
+        }
+    }
+}
+
 // an expression that defines a variable in terms of a constant
 // plus the sum of free variables times their coefficients
 #[derive(Debug, Clone)]
-pub struct AffineExpression {
-    pub dependent_variable: usize,
-    pub constant: Rational,
-    pub free_variable_coefficients: HashMap<usize, Rational>,
+pub struct AffineExpression<T = Rational> {
+    pub dependent_variable: Dependent,
+    pub constant: T,
+    pub free_variable_coefficients: HashMap<usize, T>,
 }
 
-impl AffineExpression {
+impl<T: Field> AffineExpression<T> {
     // must supply all values for all free variables
-    pub fn eval(&self, values: &HashMap<usize, Rational>) -> Rational {
-        let mut result = self.constant;
+    pub fn eval(&self, values: &HashMap<usize, T>) -> T {
+        let mut result = self.constant.clone();
 
         for (var, value) in values.iter() {
-            if self.free_variable_coefficients.contains_key(var) {
-                result += self.free_variable_coefficients[var] * *value
+            if let Some(coefficient) = self.free_variable_coefficients.get(var) {
+                result += coefficient.clone() * value.clone()
             }
         }
 
@@ -163,12 +249,12 @@ impl AffineExpression {
     }
 
     // plug in the values for the given variables and return the resulting expression
-    pub fn partial_eval(&self, values: &HashMap<usize, Rational>) -> Self {
+    pub fn partial_eval(&self, values: &HashMap<usize, T>) -> Self {
         let mut result = self.clone();
 
-        for (var, &value) in values.iter() {
+        for (var, value) in values.iter() {
             if let Some(coefficient) = result.free_variable_coefficients.remove(var) {
-                result.constant += value * coefficient;
+                result.constant += value.clone() * coefficient;
             }
         }
 
@@ -176,11 +262,11 @@ impl AffineExpression {
     }
 }
 
-impl Sum for AffineExpression {
+impl<T: Field> Sum for AffineExpression<T> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         let result = AffineExpression {
-            dependent_variable: (b's' - b'a') as usize,
-            constant: 0.into(),
+            dependent_variable: Dependent::Total,
+            constant: T::zero(),
             free_variable_coefficients: HashMap::new(),
         };
 
@@ -190,77 +276,96 @@ impl Sum for AffineExpression {
                 *result
                     .free_variable_coefficients
                     .entry(i)
-                    .or_insert(0.into()) += c;
+                    .or_insert_with(T::zero) += c;
             }
             result
         })
     }
 }
 
-impl fmt::Display for AffineExpression {
+impl<T: Scalar> AffineExpression<T> {
+    // display the expression with its variables named by names
+    pub fn named<'a>(&'a self, names: &'a VariableNames) -> NamedExpression<'a, T> {
+        NamedExpression {
+            expression: self,
+            names,
+        }
+    }
+}
+
+pub struct NamedExpression<'a, T> {
+    expression: &'a AffineExpression<T>,
+    names: &'a VariableNames,
+}
+
+impl<T: Scalar> fmt::Display for NamedExpression<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let dep = var(self.dependent_variable);
-        let mut free: Vec<(usize, Rational)> = self
+        let dep = self.names.dependent(self.expression.dependent_variable);
+        let mut free: Vec<(usize, &T)> = self
+            .expression
             .free_variable_coefficients
             .iter()
-            .map(|(i, c)| (*i, *c))
+            .map(|(i, c)| (*i, c))
             .collect();
 
         free.sort_unstable_by_key(|(i, _)| *i);
 
         let free_equation = free
             .iter()
-            .map(|(i, c)| free_var(*i, *c))
+            .map(|(i, c)| free_var(&self.names.name(*i), *c))
             .collect::<Vec<String>>()
             .join(" ");
 
-        write!(f, "{dep} = {} {free_equation}", self.constant)
+        write!(f, "{dep} = {} {free_equation}", self.expression.constant)
     }
 }
 
-fn var(index: usize) -> char {
-    (b'a' + index as u8) as char
+impl<T: Scalar> fmt::Display for AffineExpression<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.named(&VariableNames::Indexed))
+    }
 }
 
-fn free_var(index: usize, coefficient: Rational) -> String {
-    let sign = if coefficient.numerator < 0 { '-' } else { '+' };
+fn free_var<T: Scalar>(name: &str, coefficient: &T) -> String {
+    let sign = if *coefficient < 0.into() { '-' } else { '+' };
     let coef = coefficient.abs();
     let coef = if coef == 1.into() {
         "".into()
     } else {
         format!("{coef}")
     };
-    format!("{} {}{}", sign, coef, var(index))
+    format!("{sign} {coef}{name}")
 }
 
-pub fn extract_parametric_solution(
-    rref_matrix: &[Vec<Rational>],
+pub fn extract_parametric_solution<T: Field>(
+    rref_system: &LinearSystem<T>,
     pivot_data: &PivotData,
-) -> Vec<AffineExpression> {
-    let rhs_col = rref_matrix[0].len() - 1;
+) -> Vec<AffineExpression<T>> {
+    let rref_matrix = rref_system.augmented();
+    let rhs_col = rref_system.variables();
 
     (0..rhs_col)
         .map(|col| {
             // check if it's a pivot col or free col
             if pivot_data.pivot_row_for_col.contains_key(&col) {
                 let row = pivot_data.pivot_row_for_col[&col];
-                let constant = rref_matrix[row][rhs_col];
+                let constant = rref_matrix[(row, rhs_col)].clone();
                 let free_variable_coefficients = pivot_data
                     .free_columns
                     .iter()
-                    .map(|free_col| (*free_col, -rref_matrix[row][*free_col]))
-                    .filter(|(_free_col, coefficient)| *coefficient != 0.into())
+                    .map(|free_col| (*free_col, -rref_matrix[(row, *free_col)].clone()))
+                    .filter(|(_free_col, coefficient)| !coefficient.is_zero())
                     .collect();
                 AffineExpression {
-                    dependent_variable: col,
+                    dependent_variable: Dependent::Variable(col),
                     constant,
                     free_variable_coefficients,
                 }
             } else {
-                let constant = 0.into();
-                let free_variable_coefficients = HashMap::from([(col, 1.into())]);
+                let constant = T::zero();
+                let free_variable_coefficients = HashMap::from([(col, T::one())]);
                 AffineExpression {
-                    dependent_variable: col,
+                    dependent_variable: Dependent::Variable(col),
                     constant,
                     free_variable_coefficients,
                 }
@@ -271,59 +376,320 @@ pub fn extract_parametric_solution(
 
 // derive min/max bounds from the parametric solution for the given free variable
 // if there are no affine expressions that only contain the one free variable,
-// no bounds will be returned other than the defaults
-pub fn free_variable_bounds(
-    parametric_solution: &[AffineExpression],
+// no bounds will be returned other than the defaults. each bound comes from the
+// exact floor or ceil of -C/A, so a fractional one never rounds the wrong way
+pub fn free_variable_bounds<T: Scalar>(
+    parametric_solution: &[AffineExpression<T>],
     free_variable: usize,
-    min: isize,
-    max: isize,
-    values: &HashMap<usize, Rational>,
-) -> (isize, isize) {
-    let mut min: isize = min;
-    let mut max: isize = max;
+    min: T,
+    max: T,
+    values: &HashMap<usize, T>,
+) -> (T, T) {
+    let mut min = min;
+    let mut max = max;
 
     for affine_expression in parametric_solution {
-        let simplified_expression = affine_expression.partial_eval(&values);
+        let simplified_expression = affine_expression.partial_eval(values);
 
         if simplified_expression.free_variable_coefficients.len() > 1 {
             continue;
         }
-        if let Some(&coefficient) = simplified_expression
+        if let Some(coefficient) = simplified_expression
             .free_variable_coefficients
             .get(&free_variable)
+            .cloned()
         {
+            let constant = simplified_expression.constant;
             // if A > 0, free var lower bound is >= ceil(-C/A)
             // if A < 0, free var upper bound is <= floor(-C/A)
-            if coefficient > Rational::from(0) {
-                let new_min = (-simplified_expression.constant / coefficient).ceil();
-                if new_min > min {
-                    min = new_min;
+            if coefficient > T::zero() {
+                min = min.max((-constant / coefficient).ceil());
+            } else if coefficient < T::zero() {
+                max = max.min((-constant / coefficient).floor());
+            }
+        }
+    }
+
+    (min, max)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    AtMost,
+    Equal,
+    AtLeast,
+}
+
+// coefficients . x (relation) rhs
+#[derive(Debug, Clone)]
+pub struct Constraint<T = Rational> {
+    pub coefficients: Vec<T>,
+    pub relation: Relation,
+    pub rhs: T,
+}
+
+impl<T: Scalar> Constraint<T> {
+    pub fn new(coefficients: Vec<T>, relation: Relation, rhs: T) -> Self {
+        Self {
+            coefficients,
+            relation,
+            rhs,
+        }
+    }
+}
+
+// the result of minimising a linear program. basis holds columns of the standard
+// form, which are the variables followed by one slack for each inequality in order
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinearProgram<T = Rational> {
+    // duals has one multiplier per constraint (<= 0 for AtMost, >= 0 for AtLeast)
+    // such that duals . rhs == objective and objective - duals . A >= 0, proving
+    // nothing feasible does better
+    Optimal {
+        values: Vec<T>,
+        objective: T,
+        basis: Vec<usize>,
+        duals: Vec<T>,
+    },
+    // multipliers with the same signs as duals, where multipliers . A <= 0 but
+    // multipliers . rhs > 0, which no x >= 0 can satisfy
+    Infeasible {
+        multipliers: Vec<T>,
+    },
+    // values is feasible and the objective falls forever moving along direction
+    Unbounded {
+        values: Vec<T>,
+        direction: Vec<T>,
+    },
+}
+
+// minimise objective . x subject to the constraints and x >= 0 with the two phase
+// simplex method, using bland's rule (lowest index enters and leaves) so it can't cycle
+pub fn minimise<T: Scalar>(objective: &[T], constraints: &[Constraint<T>]) -> LinearProgram<T> {
+    let vars = objective.len();
+    let slacks = constraints
+        .iter()
+        .filter(|constraint| constraint.relation != Relation::Equal)
+        .count();
+    let artificials = constraints.len();
+    let standard = vars + slacks;
+    let rhs = standard + artificials;
+
+    // build [A | slacks | artificials | b] with every b >= 0, so that the artificial
+    // variables make a feasible starting basis, remembering which rows were negated
+    let mut flipped = vec![false; constraints.len()];
+    let mut slack = vars;
+    let mut tableau: Vec<Vec<T>> = constraints
+        .iter()
+        .enumerate()
+        .map(|(i, constraint)| {
+            let mut row = vec![T::zero(); rhs + 1];
+            row[..vars].clone_from_slice(&constraint.coefficients[..vars]);
+            match constraint.relation {
+                Relation::AtMost => row[slack] = T::one(),
+                Relation::AtLeast => row[slack] = -T::one(),
+                Relation::Equal => {}
+            }
+            if constraint.relation != Relation::Equal {
+                slack += 1;
+            }
+            row[rhs] = constraint.rhs.clone();
+            if row[rhs] < T::zero() {
+                flipped[i] = true;
+                for value in row.iter_mut() {
+                    *value = -value.clone();
                 }
-            } else if coefficient < 0.into() {
-                let new_max = (-simplified_expression.constant / coefficient).floor();
-                if new_max < max {
-                    max = new_max;
+            }
+            row[standard + i] = T::one();
+            row
+        })
+        .collect();
+    let mut basis: Vec<usize> = (standard..rhs).collect();
+
+    // the constraint multipliers c_B B^-1 can be read off the artificial columns,
+    // which started out as the identity
+    let multipliers = |tableau: &[Vec<T>], basis: &[usize], costs: &[T]| -> Vec<T> {
+        (0..artificials)
+            .map(|i| {
+                let y: T = basis
+                    .iter()
+                    .zip(tableau)
+                    .map(|(var, row)| costs[*var].clone() * row[standard + i].clone())
+                    .sum();
+                if flipped[i] {
+                    -y
+                } else {
+                    y
+                }
+            })
+            .collect()
+    };
+
+    // phase one: minimise the sum of the artificial variables
+    let phase_one_costs: Vec<T> = (0..rhs)
+        .map(|col| if col < standard { T::zero() } else { T::one() })
+        .collect();
+    if run_simplex(&mut tableau, &mut basis, &phase_one_costs, rhs).is_some() {
+        unreachable!("phase one is bounded below by zero");
+    }
+    let infeasibility: T = basis
+        .iter()
+        .zip(tableau.iter())
+        .filter(|(var, _)| **var >= standard)
+        .map(|(_, row)| row[rhs].clone())
+        .sum();
+    if !infeasibility.is_zero() {
+        // phase one's reduced costs are all non-negative, so its multipliers combine
+        // the constraints into something <= 0 that has to equal the positive optimum
+        let multipliers = multipliers(&tableau, &basis, &phase_one_costs);
+        return LinearProgram::Infeasible { multipliers };
+    }
+
+    // drive any artificial variables left in the basis (at zero) out of it, and
+    // drop rows where that's impossible since they were redundant
+    let mut row = 0;
+    while row < tableau.len() {
+        if basis[row] >= standard {
+            match (0..standard).find(|col| !tableau[row][*col].is_zero()) {
+                Some(col) => simplex_pivot(&mut tableau, &mut basis, row, col),
+                None => {
+                    tableau.remove(row);
+                    basis.remove(row);
+                    continue;
                 }
             }
         }
+        row += 1;
     }
 
-    (min, max)
+    // phase two: minimise the real costs, never letting an artificial back in
+    let mut costs = objective.to_vec();
+    costs.resize(rhs, T::zero());
+    let unbounded = run_simplex(&mut tableau, &mut basis, &costs, standard);
+
+    let mut values = vec![T::zero(); vars];
+    for (row, var) in basis.iter().enumerate() {
+        if *var < vars {
+            values[*var] = tableau[row][rhs].clone();
+        }
+    }
+
+    if let Some(entering) = unbounded {
+        // raising the entering variable only ever raises the basic variables. the
+        // entering column may be a slack, so the ray is built over every standard
+        // column and then cut back to the real variables
+        let mut direction = vec![T::zero(); standard];
+        direction[entering] = T::one();
+        for (row, var) in basis.iter().enumerate() {
+            if *var < standard {
+                direction[*var] = -tableau[row][entering].clone();
+            }
+        }
+        direction.truncate(vars);
+        return LinearProgram::Unbounded { values, direction };
+    }
+
+    LinearProgram::Optimal {
+        objective: values
+            .iter()
+            .zip(objective)
+            .map(|(value, cost)| value.clone() * cost.clone())
+            .sum(),
+        duals: multipliers(&tableau, &basis, &costs),
+        values,
+        basis,
+    }
+}
+
+// pivot until no column below entering_limit has a negative reduced cost. returns
+// the entering column if the objective turns out to be unbounded along it
+fn run_simplex<T: Scalar>(
+    tableau: &mut [Vec<T>],
+    basis: &mut [usize],
+    costs: &[T],
+    entering_limit: usize,
+) -> Option<usize> {
+    let rhs = tableau.first().map_or(0, |row| row.len() - 1);
+
+    loop {
+        let reduced_cost = |col: usize| {
+            costs[col].clone()
+                - basis
+                    .iter()
+                    .zip(tableau.iter())
+                    .map(|(var, row)| costs[*var].clone() * row[col].clone())
+                    .sum::<T>()
+        };
+
+        let entering = (0..entering_limit).find(|col| reduced_cost(*col) < T::zero())?;
+
+        // ratio test, breaking ties by the lowest basis variable
+        let mut leaving: Option<(usize, T)> = None;
+        for row in 0..tableau.len() {
+            let coefficient = &tableau[row][entering];
+            if *coefficient <= T::zero() {
+                continue;
+            }
+            let ratio = tableau[row][rhs].clone() / coefficient.clone();
+            let better = match &leaving {
+                None => true,
+                Some((best_row, best_ratio)) => {
+                    ratio < *best_ratio || (ratio == *best_ratio && basis[row] < basis[*best_row])
+                }
+            };
+            if better {
+                leaving = Some((row, ratio));
+            }
+        }
+
+        match leaving {
+            Some((row, _)) => simplex_pivot(tableau, basis, row, entering),
+            None => return Some(entering),
+        }
+    }
+}
+
+fn simplex_pivot<T: Field>(
+    tableau: &mut [Vec<T>],
+    basis: &mut [usize],
+    pivot_row: usize,
+    col: usize,
+) {
+    let p = tableau[pivot_row][col].clone();
+    for value in tableau[pivot_row].iter_mut() {
+        *value /= p.clone();
+    }
+    for row in 0..tableau.len() {
+        if row == pivot_row {
+            continue;
+        }
+        let factor = tableau[row][col].clone();
+        if factor.is_zero() {
+            continue;
+        }
+        for c in 0..tableau[row].len() {
+            let pivot_row_val = tableau[pivot_row][c].clone();
+            tableau[row][c] -= factor.clone() * pivot_row_val;
+        }
+    }
+    basis[pivot_row] = col;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::big_rational::BigRational;
+    use crate::prime_field::Gf2;
 
     #[test]
     fn sum_of_affine_expressions() {
         let a = AffineExpression {
-            dependent_variable: 0,
+            dependent_variable: Dependent::Variable(0),
             constant: 5.into(),
             free_variable_coefficients: HashMap::from([(2, Rational::from(-1))]),
         };
         let b = AffineExpression {
-            dependent_variable: 1,
+            dependent_variable: Dependent::Variable(1),
             constant: Rational::from(-7),
             free_variable_coefficients: HashMap::from([
                 (2, Rational::from(-7)),
@@ -333,13 +699,13 @@ mod tests {
 
         let sum: AffineExpression = [a, b].into_iter().sum();
 
-        assert_eq!(format!("{sum}"), String::from("s = -2 - 8c + 2d"));
+        assert_eq!(format!("{sum}"), String::from("total = -2 - 8x2 + 2x3"));
     }
 
     #[test]
     fn eval_affine_expression() {
         let a = AffineExpression {
-            dependent_variable: 0,
+            dependent_variable: Dependent::Variable(0),
             constant: 5.into(),
             free_variable_coefficients: HashMap::from([(2, Rational::from(-1))]),
         };
@@ -352,7 +718,7 @@ mod tests {
     #[test]
     fn partial_eval_affine_expression() {
         let a = AffineExpression {
-            dependent_variable: 0,
+            dependent_variable: Dependent::Variable(0),
             constant: Rational::from(-7),
             free_variable_coefficients: HashMap::from([
                 (1, Rational::from(-7)),
@@ -362,6 +728,248 @@ mod tests {
 
         let a_partial = a.partial_eval(&HashMap::from([(1, Rational::from(5))]));
 
-        assert_eq!(format!("{a_partial}"), String::from("a = -42 + 2c"));
+        assert_eq!(format!("{a_partial}"), String::from("x0 = -42 + 2x2"));
+    }
+
+    #[test]
+    fn naive_bounds_from_the_rhs() {
+        // x0 + x1 = 3 and x1 + 2x2 = 10
+        let system =
+            LinearSystem::from_augmented(vec![rationals(&[1, 1, 0, 3]), rationals(&[0, 1, 2, 10])]);
+        assert_eq!(
+            naive_max_bounds(&system),
+            HashMap::from([(0, 3.into()), (1, 3.into()), (2, 10.into())])
+        );
+
+        let empty: LinearSystem<Rational> = LinearSystem::from_augmented(vec![]);
+        assert_eq!(naive_max_bounds(&empty), HashMap::new());
+    }
+
+    #[test]
+    fn bounds_of_a_free_variable() {
+        // x0 = 7/2 - x1 and x2 = -5/2 + 2x1 - x3, with x3 = 1 already chosen
+        let parametric_solution = vec![
+            AffineExpression {
+                dependent_variable: Dependent::Variable(0),
+                constant: Rational::new(7, 2),
+                free_variable_coefficients: HashMap::from([(1, Rational::from(-1))]),
+            },
+            AffineExpression {
+                dependent_variable: Dependent::Variable(2),
+                constant: Rational::new(-5, 2),
+                free_variable_coefficients: HashMap::from([
+                    (1, Rational::from(2)),
+                    (3, Rational::from(-1)),
+                ]),
+            },
+        ];
+
+        // x0 >= 0 needs x1 <= 3, and x2 >= 0 needs x1 >= 7/4 once x3 is known
+        assert_eq!(
+            free_variable_bounds(
+                &parametric_solution,
+                1,
+                0.into(),
+                100.into(),
+                &HashMap::new()
+            ),
+            (0.into(), 3.into())
+        );
+        let values = HashMap::from([(3, Rational::from(1))]);
+        assert_eq!(
+            free_variable_bounds(&parametric_solution, 1, 0.into(), 100.into(), &values),
+            (2.into(), 3.into())
+        );
+    }
+
+    #[test]
+    fn names_beyond_26_variables() {
+        let a = AffineExpression {
+            dependent_variable: Dependent::Variable(27),
+            constant: Rational::from(3),
+            free_variable_coefficients: HashMap::from([
+                (26, Rational::from(-1)),
+                (1, Rational::from(2)),
+            ]),
+        };
+        assert_eq!(format!("{a}"), "x27 = 3 + 2x1 - x26");
+
+        let labels = VariableNames::Labels(vec!["red".into(), "green".into()]);
+        assert_eq!(format!("{}", a.named(&labels)), "x27 = 3 + 2green - x26");
+    }
+
+    #[test]
+    fn rref_without_overflow() {
+        // eliminating with coefficients this large overflows an isize numerator
+        let m = BigRational::from(isize::MAX);
+        let one = BigRational::from(1);
+        let zero = BigRational::from(0);
+        let mut equations = LinearSystem::from_augmented(vec![
+            vec![m.clone(), one.clone(), m.clone()],
+            vec![one.clone(), m.clone(), one.clone()],
+        ]);
+
+        assert_eq!(gauss_jordan_to_rref(&mut equations), Ok(2));
+        assert_eq!(
+            equations,
+            LinearSystem::from_augmented(vec![
+                vec![one.clone(), zero.clone(), one.clone()],
+                vec![zero.clone(), one.clone(), zero.clone()],
+            ])
+        );
+    }
+
+    #[test]
+    fn rref_over_f64() {
+        // 2x + y = 5, x - 3y = -1 has the solution x = 2, y = 1
+        let mut equations =
+            LinearSystem::from_augmented(vec![vec![2.0, 1.0, 5.0], vec![1.0, -3.0, -1.0]]);
+
+        gauss_jordan_to_rref(&mut equations).unwrap();
+        let pivot_data = extract_pivots(&equations);
+        let solution = extract_parametric_solution(&equations, &pivot_data);
+
+        assert!(pivot_data.free_columns.is_empty());
+        assert!((solution[0].constant - 2.0).abs() < 1e-12);
+        assert!((solution[1].constant - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rref_over_gf2() {
+        // buttons a, b and c toggle lights 0 and 1, 1 and 2, and 0 and 2, and we want 0 and 1 on
+        let [o, l] = [Gf2::new(0), Gf2::new(1)];
+        let mut equations = LinearSystem::from_augmented(vec![
+            vec![l, o, l, l],
+            vec![l, l, o, l],
+            vec![o, l, l, o],
+        ]);
+
+        gauss_jordan_to_rref(&mut equations).unwrap();
+        let pivot_data = extract_pivots(&equations);
+        let solution = extract_parametric_solution(&equations, &pivot_data);
+
+        assert_eq!(pivot_data.free_columns, vec![2]);
+        // a = 1 + c, b = c
+        assert_eq!(solution[0].constant, l);
+        assert_eq!(solution[0].free_variable_coefficients[&2], l);
+        assert_eq!(solution[1].constant, o);
+        assert_eq!(solution[1].free_variable_coefficients[&2], l);
+    }
+
+    #[test]
+    fn rref_reports_rank_and_inconsistency() {
+        // the third row is the sum of the first two, so the rank is 2
+        let mut equations = LinearSystem::from_augmented(vec![
+            rationals(&[1, 1, 0, 2]),
+            rationals(&[0, 1, 1, 3]),
+            rationals(&[1, 2, 1, 5]),
+        ]);
+        assert_eq!(gauss_jordan_to_rref(&mut equations), Ok(2));
+
+        // but changing its rhs means no solution can satisfy all three
+        let mut equations = LinearSystem::from_augmented(vec![
+            rationals(&[1, 1, 0, 2]),
+            rationals(&[0, 1, 1, 3]),
+            rationals(&[1, 2, 1, 6]),
+        ]);
+        assert_eq!(
+            gauss_jordan_to_rref(&mut equations),
+            Err(EliminationError::Inconsistent { row: 2 })
+        );
+
+        // a system without any equations has no variables and nothing to reduce
+        let mut equations: LinearSystem = LinearSystem::from_augmented(vec![]);
+        assert_eq!(equations.variables(), 0);
+        assert_eq!(gauss_jordan_to_rref(&mut equations), Ok(0));
+        assert_eq!(equations.coefficients().cols(), 0);
+    }
+
+    fn rationals(values: &[isize]) -> Vec<Rational> {
+        values.iter().map(|v| Rational::from(*v)).collect()
+    }
+
+    #[test]
+    fn simplex_optimal_with_duals() {
+        // maximise 3x + 5y with x <= 4, 2y <= 12, 3x + 2y <= 18
+        let constraints = vec![
+            Constraint::new(rationals(&[1, 0]), Relation::AtMost, 4.into()),
+            Constraint::new(rationals(&[0, 2]), Relation::AtMost, 12.into()),
+            Constraint::new(rationals(&[3, 2]), Relation::AtMost, 18.into()),
+        ];
+        let LinearProgram::Optimal {
+            values,
+            objective,
+            duals,
+            ..
+        } = minimise(&rationals(&[-3, -5]), &constraints)
+        else {
+            panic!("expected an optimal solution");
+        };
+
+        assert_eq!(values, rationals(&[2, 6]));
+        assert_eq!(objective, Rational::from(-36));
+        assert_eq!(duals, vec![0.into(), Rational::new(-3, 2), (-1).into()]);
+    }
+
+    #[test]
+    fn simplex_infeasible_with_certificate() {
+        // x + y <= 1 and x + y >= 3
+        let constraints = vec![
+            Constraint::new(rationals(&[1, 1]), Relation::AtMost, 1.into()),
+            Constraint::new(rationals(&[1, 1]), Relation::AtLeast, 3.into()),
+        ];
+        let LinearProgram::Infeasible { multipliers } = minimise(&rationals(&[1, 1]), &constraints)
+        else {
+            panic!("expected no feasible solution");
+        };
+
+        let combined: Rational = multipliers
+            .iter()
+            .zip(&constraints)
+            .map(|(y, constraint)| *y * constraint.rhs)
+            .sum();
+        assert!(combined > 0.into());
+        for col in 0..2 {
+            let combined: Rational = multipliers
+                .iter()
+                .zip(&constraints)
+                .map(|(y, constraint)| *y * constraint.coefficients[col])
+                .sum();
+            assert!(combined <= 0.into());
+        }
+    }
+
+    #[test]
+    fn simplex_unbounded() {
+        // minimise -x with x - y <= 1, which runs off along x = y
+        let constraints = vec![Constraint::new(
+            rationals(&[1, -1]),
+            Relation::AtMost,
+            1.into(),
+        )];
+        assert_eq!(
+            minimise(&rationals(&[-1, 0]), &constraints),
+            LinearProgram::Unbounded {
+                values: rationals(&[1, 0]),
+                direction: rationals(&[1, 1]),
+            }
+        );
+    }
+
+    #[test]
+    fn simplex_unbounded_along_a_slack() {
+        // minimise -x with x >= 1, where the surplus of x >= 1 is what enters
+        let constraints = vec![Constraint::new(
+            rationals(&[1]),
+            Relation::AtLeast,
+            1.into(),
+        )];
+        match minimise(&rationals(&[-1]), &constraints) {
+            LinearProgram::Unbounded { values, direction } => {
+                assert_eq!(values, rationals(&[1]));
+                assert_eq!(direction, rationals(&[1]));
+            }
+            program => panic!("expected an unbounded program, got {program:?}"),
+        }
     }
 }
@@ -0,0 +1,290 @@
+use crate::big_rational::BigRational;
+use crate::field::Field;
+use crate::ilp::minimise_integer;
+use crate::linear_algebra::{
+    extract_parametric_solution, extract_pivots, gauss_jordan_to_rref, LinearSystem, Matrix,
+};
+use crate::prime_field::Gf2;
+use common::parse::{expect_chars, parse_token};
+use common::ParseError;
+use regex::Regex;
+use std::fmt;
+use std::iter::Sum;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+static MACHINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[(.+)\] (.+) \{(.+)\}").unwrap());
+
+static BUTTON_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\(([\d,]+)\)").unwrap());
+
+// the lights search tries all 2^k combinations of k free buttons, so give up on
+// machines with more than this, which is already about 16 million combinations
+pub const MAX_FREE_BUTTONS: usize = 24;
+
+// the light toggles form a linear system over GF(2), where each button is pressed
+// either zero or one times. any solution is the particular solution plus some
+// combination of the null space basis, so search that coset for the fewest presses.
+// that takes 2^k steps for k free buttons, so only up to MAX_FREE_BUTTONS are searched
+pub fn fewest_presses_for_lights(machine: &Machine) -> Presses {
+    let mut light_equations = machine.light_equations.clone();
+    if gauss_jordan_to_rref(&mut light_equations).is_err() {
+        return Presses::Unreachable;
+    }
+    let pivot_data = extract_pivots(&light_equations);
+    let free_buttons = pivot_data.free_columns.len();
+    if free_buttons > MAX_FREE_BUTTONS {
+        return Presses::TooManyFreeButtons(free_buttons);
+    }
+    let parametric_solution = extract_parametric_solution(&light_equations, &pivot_data);
+
+    let buttons = parametric_solution.len();
+    let words = buttons.div_ceil(u64::BITS as usize);
+    let mut presses = vec![0u64; words];
+    let mut null_space: Vec<Vec<u64>> = vec![vec![0u64; words]; free_buttons];
+
+    // the parametric solution has one expression per button, in order
+    for (button, expression) in parametric_solution.iter().enumerate() {
+        let (word, bit) = (button / u64::BITS as usize, button % u64::BITS as usize);
+        if !expression.constant.is_zero() {
+            presses[word] |= 1 << bit;
+        }
+        for (i, free_col) in pivot_data.free_columns.iter().enumerate() {
+            if expression.free_variable_coefficients.contains_key(free_col) {
+                null_space[i][word] |= 1 << bit;
+            }
+        }
+    }
+
+    // walk every combination of the null space basis in gray code order so each step
+    // only adds (xors) a single basis vector
+    let count =
+        |presses: &[u64]| -> usize { presses.iter().map(|word| word.count_ones() as usize).sum() };
+    let mut min_presses = count(&presses);
+    for step in 1..(1u64 << null_space.len()) {
+        let basis_vector = &null_space[step.trailing_zeros() as usize];
+        for (word, basis_word) in presses.iter_mut().zip(basis_vector) {
+            *word ^= basis_word;
+        }
+        min_presses = min_presses.min(count(&presses));
+    }
+
+    Presses::Fewest(BigRational::from(min_presses as isize))
+}
+
+// every button press adds one to each of its joltages, so this is the integer program
+// of minimising the total presses subject to the joltage equations
+pub fn fewest_presses_for_joltage(machine: &Machine) -> Presses {
+    let mut equations = machine.equations.clone();
+    let Ok(rank) = gauss_jordan_to_rref(&mut equations) else {
+        return Presses::Unreachable;
+    };
+
+    // the pivot rows describe the same solutions, minus any redundant constraints
+    let buttons = equations.variables();
+    equations.truncate(rank);
+
+    let presses = vec![BigRational::from(1); buttons];
+    // every button raises some joltage, so no button can be pressed without limit
+    match minimise_integer(&equations, &presses).expect("button presses are bounded") {
+        Some((_, min_presses)) => Presses::Fewest(min_presses),
+        None => Presses::Unreachable,
+    }
+}
+
+// the fewest presses for one or more machines, which is unreachable if any of them are
+// and otherwise unknown if any of them had too many free buttons to search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Presses {
+    Fewest(BigRational),
+    Unreachable,
+    TooManyFreeButtons(usize),
+}
+
+impl fmt::Display for Presses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Presses::Fewest(presses) => write!(f, "{presses}"),
+            Presses::Unreachable => write!(f, "unreachable"),
+            Presses::TooManyFreeButtons(free_buttons) => write!(
+                f,
+                "unknown, {free_buttons} free buttons is more than the {MAX_FREE_BUTTONS} we can search"
+            ),
+        }
+    }
+}
+
+impl Sum for Presses {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Presses::Fewest(0.into()), |total, presses| {
+            match (total, presses) {
+                (Presses::Fewest(a), Presses::Fewest(b)) => Presses::Fewest(a + b),
+                (Presses::Unreachable, _) | (_, Presses::Unreachable) => Presses::Unreachable,
+                (Presses::TooManyFreeButtons(free_buttons), _)
+                | (_, Presses::TooManyFreeButtons(free_buttons)) => {
+                    Presses::TooManyFreeButtons(free_buttons)
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Machine {
+    light_equations: LinearSystem<Gf2>, // which buttons toggle each light
+    equations: LinearSystem<BigRational>, // the constraint equations on the joltages
+}
+
+impl FromStr for Machine {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, [lights, buttons_str, joltages_str]) = MACHINE_REGEX
+            .captures(s)
+            .ok_or_else(|| {
+                ParseError::at(s, s, "expected a machine like [.##.] (3) (1,3) {3,5,4,7}")
+            })?
+            .extract();
+
+        expect_chars(s, lights, |c| matches!(c, '.' | '#'), "a light '.' or '#'")?;
+        // only whitespace may sit between the buttons, rather than skipping over the rest
+        if let Some(stray) = BUTTON_REGEX
+            .split(buttons_str)
+            .map(str::trim)
+            .find(|gap| !gap.is_empty())
+        {
+            return Err(ParseError::at(s, stray, "expected a button like (0,2)"));
+        }
+        let buttons: Vec<Vec<usize>> = BUTTON_REGEX
+            .captures_iter(buttons_str)
+            .map(|captures| captures.extract::<1>().1[0])
+            .map(|button| {
+                button
+                    .split(',')
+                    .map(|light_index_str| {
+                        let light_index: usize = parse_token(s, light_index_str, "a light index")?;
+                        if light_index >= lights.len() {
+                            return Err(ParseError::at(
+                                s,
+                                light_index_str,
+                                format!("expected a light index below {}", lights.len()),
+                            ));
+                        }
+                        Ok(light_index)
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        let joltages: Vec<u32> = joltages_str
+            .split(',')
+            .map(|joltage| parse_token(s, joltage, "a joltage"))
+            .collect::<Result<_, _>>()?;
+        if joltages.len() != lights.len() {
+            return Err(ParseError::at(
+                s,
+                joltages_str,
+                format!("expected {} joltages, one per light", lights.len()),
+            ));
+        }
+
+        // both systems share the same coefficients: which lights each button affects
+        let mut light_toggles = Matrix::zeros(lights.len(), buttons.len());
+        let mut joltage_increases = Matrix::zeros(joltages.len(), buttons.len());
+
+        for (button_index, button) in buttons.iter().enumerate() {
+            for light_index in button.iter() {
+                light_toggles[(*light_index, button_index)] = Gf2::one();
+                joltage_increases[(*light_index, button_index)] = 1.into();
+            }
+        }
+
+        let light_equations = LinearSystem::new(
+            light_toggles,
+            lights
+                .bytes()
+                .map(|light| Gf2::from((light == b'#') as isize))
+                .collect(),
+        );
+        let equations = LinearSystem::new(
+            joltage_increases,
+            joltages
+                .iter()
+                .map(|joltage| BigRational::from(*joltage as isize))
+                .collect(),
+        );
+
+        Ok(Self {
+            light_equations,
+            equations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stray_text_between_buttons() {
+        let err = "[.#] (0) garbage (1) {1,2}".parse::<Machine>().unwrap_err();
+        assert_eq!(
+            (err.column, err.token.as_str(), err.message.as_str()),
+            (10, "garbage", "expected a button like (0,2)")
+        );
+        assert!("[.#] (0)  (1) {1,2}".parse::<Machine>().is_ok());
+    }
+
+    #[test]
+    fn lights_beyond_32_bits() {
+        // 40 lights that are all on, with one button per light plus one that toggles
+        // every light, so a single press is enough
+        let lights = "#".repeat(40);
+        let singles: Vec<String> = (0..40).map(|i| format!("({i})")).collect();
+        let all = (0..40).map(|i| i.to_string()).collect::<Vec<_>>().join(",");
+        let joltages = vec!["1"; 40].join(",");
+        let machine: Machine = format!("[{lights}] {} ({all}) {{{joltages}}}", singles.join(" "))
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            fewest_presses_for_lights(&machine),
+            Presses::Fewest(1.into())
+        );
+    }
+
+    #[test]
+    fn too_many_free_buttons() {
+        // every one of 26 buttons toggles the only light, leaving 25 of them free
+        let buttons = vec!["(0)"; 26].join(" ");
+        let machine: Machine = format!("[#] {buttons} {{1}}").parse().unwrap();
+        let presses = fewest_presses_for_lights(&machine);
+        assert_eq!(presses, Presses::TooManyFreeButtons(25));
+
+        // an unreachable machine still makes the total unreachable
+        let total: Presses = [presses, Presses::Unreachable].into_iter().sum();
+        assert_eq!(total, Presses::Unreachable);
+    }
+
+    #[test]
+    fn unreachable_lights() {
+        // the only button toggles the light that should stay off
+        let machine: Machine = "[#.] (1) {1,1}".parse().unwrap();
+        assert_eq!(fewest_presses_for_lights(&machine), Presses::Unreachable);
+    }
+
+    #[test]
+    fn unreachable_joltages() {
+        // both buttons raise both joltages together, so they can never differ
+        let machine: Machine = "[.#] (0,1) (0,1) {3,5}".parse().unwrap();
+        assert_eq!(fewest_presses_for_joltage(&machine), Presses::Unreachable);
+
+        // consistent over the rationals, but needs half a press of every button
+        let machine: Machine = "[...] (0,1) (1,2) (0,2) {1,1,1}".parse().unwrap();
+        assert_eq!(fewest_presses_for_joltage(&machine), Presses::Unreachable);
+
+        // and a negative press of the second button
+        let machine: Machine = "[..] (0,1) (0) {1,2}".parse().unwrap();
+        assert_eq!(fewest_presses_for_joltage(&machine), Presses::Unreachable);
+    }
+}
@@ -0,0 +1,399 @@
+use std::fmt;
+use std::ops::{Index, IndexMut, Mul};
+
+use crate::field::Field;
+use crate::rational::Rational;
+
+// a dense matrix over a field, stored as a vec of rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<T = Rational> {
+    data: Vec<Vec<T>>,
+    cols: usize,
+}
+
+impl<T: Field> Matrix<T> {
+    // the columns are counted from the first row, so a matrix without any rows has
+    // none. use from_rows when there may not be any rows
+    pub fn new(data: Vec<Vec<T>>) -> Self {
+        let cols = data.first().map_or(0, |row| row.len());
+        Self::from_rows(data, cols)
+    }
+
+    pub fn from_rows(data: Vec<Vec<T>>, cols: usize) -> Self {
+        assert!(
+            data.iter().all(|row| row.len() == cols),
+            "every row of a matrix must be the same length"
+        );
+        Self { data, cols }
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self::from_rows(vec![vec![T::zero(); cols]; rows], cols)
+    }
+
+    pub fn identity(size: usize) -> Self {
+        let mut identity = Self::zeros(size, size);
+        for i in 0..size {
+            identity[(i, i)] = T::one();
+        }
+        identity
+    }
+
+    pub fn rows(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row]
+    }
+
+    pub fn column(&self, col: usize) -> Vec<T> {
+        self.data.iter().map(|row| row[col].clone()).collect()
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.iter().map(|row| &row[..])
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+    }
+
+    pub fn truncate_rows(&mut self, rows: usize) {
+        self.data.truncate(rows);
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_rows(
+            (0..self.cols).map(|col| self.column(col)).collect(),
+            self.rows(),
+        )
+    }
+
+    pub fn mul_vector(&self, vector: &[T]) -> Vec<T> {
+        assert_eq!(
+            vector.len(),
+            self.cols,
+            "vector length must match the columns"
+        );
+        self.data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(vector)
+                    .fold(T::zero(), |sum, (a, b)| sum + a.clone() * b.clone())
+            })
+            .collect()
+    }
+
+    // reduce to reduced row echelon form in place, only choosing pivots from the first
+    // pivot_cols columns so that anything after them (like a rhs) is carried along.
+    // returns the pivot column of each pivot row, which end up first
+    pub fn row_reduce(&mut self, pivot_cols: usize) -> Vec<usize> {
+        self.eliminate(pivot_cols).pivot_cols
+    }
+
+    fn eliminate(&mut self, pivot_cols: usize) -> Elimination<T> {
+        let rows = self.rows();
+        let mut pivots = vec![];
+        let mut determinant = T::one();
+        let mut pivot_row = 0;
+
+        for pivot_col in 0..pivot_cols {
+            if pivot_row == rows {
+                break;
+            }
+
+            // find pivot
+            let mut best: Option<usize> = None;
+            for row in pivot_row..rows {
+                let val = &self.data[row][pivot_col];
+                if val.is_zero() {
+                    continue;
+                }
+                match best {
+                    Some(best_row)
+                        if !val.is_better_pivot_than(&self.data[best_row][pivot_col]) => {}
+                    _ => best = Some(row),
+                }
+            }
+            let Some(new_pivot_row) = best else {
+                continue;
+            };
+
+            if new_pivot_row != pivot_row {
+                self.swap_rows(pivot_row, new_pivot_row);
+                determinant = -determinant;
+            }
+            // normalize
+            let p = self.data[pivot_row][pivot_col].clone();
+            determinant *= p.clone();
+            if !p.is_one() {
+                for col in 0..self.cols {
+                    self.data[pivot_row][col] /= p.clone();
+                }
+            }
+            // eliminate that column everywhere else above and below
+            for row in 0..rows {
+                if row == pivot_row {
+                    continue;
+                }
+
+                let factor = self.data[row][pivot_col].clone();
+                if factor.is_zero() {
+                    continue;
+                }
+
+                for col in 0..self.cols {
+                    let pivot_row_val = self.data[pivot_row][col].clone();
+                    self.data[row][col] -= factor.clone() * pivot_row_val;
+                    // snap anything within tolerance to an exact zero so it can't become a pivot
+                    if self.data[row][col].is_zero() {
+                        self.data[row][col] = T::zero();
+                    }
+                }
+            }
+
+            pivots.push(pivot_col);
+            pivot_row += 1;
+        }
+
+        Elimination {
+            pivot_cols: pivots,
+            determinant,
+        }
+    }
+
+    // the reduced row echelon form and its pivot columns
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        let mut rref = self.clone();
+        let pivot_cols = rref.row_reduce(self.cols);
+        (rref, pivot_cols)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    pub fn determinant(&self) -> T {
+        assert_eq!(
+            self.rows(),
+            self.cols,
+            "only square matrices have a determinant"
+        );
+        let elimination = self.clone().eliminate(self.cols);
+        if elimination.pivot_cols.len() < self.cols {
+            T::zero()
+        } else {
+            elimination.determinant
+        }
+    }
+
+    // reduce [A | I] to [I | A^-1], or None if A is singular
+    pub fn inverse(&self) -> Option<Self> {
+        assert_eq!(
+            self.rows(),
+            self.cols,
+            "only square matrices have an inverse"
+        );
+        let size = self.cols;
+        let identity = Self::identity(size);
+        let mut augmented = Self::from_rows(
+            self.data
+                .iter()
+                .zip(identity.data)
+                .map(|(row, identity_row)| [row.clone(), identity_row].concat())
+                .collect(),
+            2 * size,
+        );
+
+        if augmented.row_reduce(size).len() < size {
+            return None;
+        }
+
+        Some(Self::from_rows(
+            augmented
+                .data
+                .into_iter()
+                .map(|row| row[size..].to_vec())
+                .collect(),
+            size,
+        ))
+    }
+
+    // a basis for the vectors x with A x = 0, one per free column
+    pub fn null_space(&self) -> Vec<Vec<T>> {
+        let (rref, pivot_cols) = self.rref();
+
+        (0..self.cols)
+            .filter(|col| !pivot_cols.contains(col))
+            .map(|free_col| {
+                let mut vector = vec![T::zero(); self.cols];
+                vector[free_col] = T::one();
+                for (row, pivot_col) in pivot_cols.iter().enumerate() {
+                    vector[*pivot_col] = -rref[(row, free_col)].clone();
+                }
+                vector
+            })
+            .collect()
+    }
+
+    // a basis for the vectors A x, made of the original pivot columns
+    pub fn column_space(&self) -> Vec<Vec<T>> {
+        let (_, pivot_cols) = self.rref();
+        pivot_cols.iter().map(|col| self.column(*col)).collect()
+    }
+}
+
+struct Elimination<T> {
+    pivot_cols: Vec<usize>,
+    // the product of the pivots, negated for each row swap
+    determinant: T,
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.data[row][col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        &mut self.data[row][col]
+    }
+}
+
+impl<T: Field> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(
+            self.cols,
+            other.rows(),
+            "columns on the left must match rows on the right"
+        );
+        let columns: Vec<Vec<T>> = (0..other.cols).map(|col| other.column(col)).collect();
+        Matrix::from_rows(
+            self.data
+                .iter()
+                .map(|row| {
+                    columns
+                        .iter()
+                        .map(|column| {
+                            row.iter()
+                                .zip(column)
+                                .fold(T::zero(), |sum, (a, b)| sum + a.clone() * b.clone())
+                        })
+                        .collect()
+                })
+                .collect(),
+            other.cols,
+        )
+    }
+}
+
+// each row on its own line with the columns right aligned
+impl<T: Field> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, &self.data, None)
+    }
+}
+
+// shared with augmented matrices, which draw a bar before the column at split
+pub(crate) fn write_rows<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    data: &[Vec<T>],
+    split: Option<usize>,
+) -> fmt::Result {
+    let cells: Vec<Vec<String>> = data
+        .iter()
+        .map(|row| row.iter().map(|val| val.to_string()).collect())
+        .collect();
+    let cols = cells.first().map_or(0, |row| row.len());
+    let widths: Vec<usize> = (0..cols)
+        .map(|col| cells.iter().map(|row| row[col].len()).max().unwrap_or(0))
+        .collect();
+
+    for row in cells {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, width))| {
+                let bar = if split == Some(col) { "| " } else { "" };
+                format!("{bar}{cell:>width$}")
+            })
+            .collect::<Vec<String>>()
+            .join("  ");
+        writeln!(f, "[ {line} ]")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(rows: &[&[isize]]) -> Matrix {
+        Matrix::new(
+            rows.iter()
+                .map(|row| row.iter().map(|v| Rational::from(*v)).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn rank_determinant_and_inverse() {
+        let a = matrix(&[&[2, 1], &[5, 3]]);
+        assert_eq!(a.rank(), 2);
+        assert_eq!(a.determinant(), Rational::from(1));
+        let inverse = a.inverse().unwrap();
+        assert_eq!(inverse, matrix(&[&[3, -1], &[-5, 2]]));
+        assert_eq!(&a * &inverse, Matrix::identity(2));
+
+        // swapping the rows flips the sign
+        let b = matrix(&[&[0, 2], &[3, 1]]);
+        assert_eq!(b.determinant(), Rational::from(-6));
+
+        let singular = matrix(&[&[1, 2], &[2, 4]]);
+        assert_eq!(singular.rank(), 1);
+        assert_eq!(singular.determinant(), Rational::from(0));
+        assert!(singular.inverse().is_none());
+    }
+
+    #[test]
+    fn null_and_column_spaces() {
+        // the third column is the sum of the first two
+        let a = matrix(&[&[1, 0, 1], &[0, 1, 1], &[1, 1, 2]]);
+
+        let null_space = a.null_space();
+        assert_eq!(null_space, vec![matrix(&[&[-1, -1, 1]]).row(0).to_vec()]);
+        assert!(a.mul_vector(&null_space[0]).iter().all(|val| val.is_zero()));
+
+        assert_eq!(a.column_space(), vec![a.column(0), a.column(1)]);
+    }
+
+    #[test]
+    fn transpose_and_display() {
+        let a = matrix(&[&[1, -20, 3]]);
+        assert_eq!(a.transpose(), matrix(&[&[1], &[-20], &[3]]));
+        assert_eq!(format!("{a}"), "[ 1  -20  3 ]\n");
+        assert_eq!(format!("{}", a.transpose()), "[   1 ]\n[ -20 ]\n[   3 ]\n");
+    }
+
+    #[test]
+    fn matrices_without_rows_keep_their_columns() {
+        let empty: Matrix = Matrix::zeros(0, 3);
+        assert_eq!((empty.rows(), empty.cols()), (0, 3));
+        assert_eq!(empty.transpose(), Matrix::zeros(3, 0));
+        assert_eq!(empty.transpose().transpose(), empty);
+        assert_eq!(empty.null_space().len(), 3);
+    }
+}
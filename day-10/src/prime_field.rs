@@ -0,0 +1,159 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::field::Field;
+
+// integers modulo the prime P
+#[derive(Eq, PartialEq, Clone, Copy, Hash)]
+pub struct PrimeField<const P: u64> {
+    pub value: u64,
+}
+
+// the field of light toggles, where adding is xor and every value is its own negative
+pub type Gf2 = PrimeField<2>;
+
+impl<const P: u64> PrimeField<P> {
+    pub fn new(value: u64) -> Self {
+        Self { value: value % P }
+    }
+
+    pub fn pow(self, mut exponent: u64) -> Self {
+        let mut base = self;
+        let mut result = Self::new(1);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    // by fermat's little theorem a^(P-1) = 1, so a^(P-2) is a's inverse
+    pub fn inverse(self) -> Self {
+        assert!(self.value != 0, "zero has no inverse");
+        self.pow(P - 2)
+    }
+}
+
+impl<const P: u64> From<isize> for PrimeField<P> {
+    fn from(num: isize) -> Self {
+        Self::new(num.rem_euclid(P as isize) as u64)
+    }
+}
+
+impl<const P: u64> fmt::Display for PrimeField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const P: u64> fmt::Debug for PrimeField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {P})", self.value)
+    }
+}
+
+impl<const P: u64> Add for PrimeField<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.value + rhs.value)
+    }
+}
+
+impl<const P: u64> AddAssign for PrimeField<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u64> Sub for PrimeField<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.value + P - rhs.value)
+    }
+}
+
+impl<const P: u64> SubAssign for PrimeField<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u64> Mul for PrimeField<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(((self.value as u128 * rhs.value as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> MulAssign for PrimeField<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u64> Div for PrimeField<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
+impl<const P: u64> DivAssign for PrimeField<P> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const P: u64> Neg for PrimeField<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(P - self.value)
+    }
+}
+
+impl<const P: u64> Field for PrimeField<P> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn one() -> Self {
+        Self::new(1)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type F7 = PrimeField<7>;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(F7::new(5) + F7::new(4), F7::new(2));
+        assert_eq!(F7::new(2) - F7::new(5), F7::new(4));
+        assert_eq!(F7::new(3) * F7::new(5), F7::new(1));
+        assert_eq!(F7::new(1) / F7::new(3), F7::new(5));
+        assert_eq!(-F7::new(3), F7::new(4));
+        assert_eq!(F7::from(-1), F7::new(6));
+    }
+
+    #[test]
+    fn gf2() {
+        assert_eq!(Gf2::new(1) + Gf2::new(1), Gf2::new(0));
+        assert_eq!(-Gf2::new(1), Gf2::new(1));
+        assert_eq!(Gf2::new(1).inverse(), Gf2::new(1));
+    }
+}
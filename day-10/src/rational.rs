@@ -1,10 +1,16 @@
 use std::cmp::{Ord, Ordering, PartialOrd};
+use std::collections::HashMap;
 use std::convert::{From, TryFrom};
 use std::fmt;
 use std::iter::Sum;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
-#[derive(Eq, PartialEq, Clone, Copy)]
+use common::parse::parse_token;
+use common::ParseError;
+
+// always kept in lowest terms with a positive denominator, so equal values hash equally
+#[derive(Eq, PartialEq, Clone, Copy, Hash)]
 pub struct Rational {
     pub numerator: isize,
     pub denominator: isize,
@@ -40,6 +46,75 @@ impl fmt::Display for Rational {
     }
 }
 
+// parses integers like "-5", fractions like "3/4" and decimals like "-1.25" exactly
+impl FromStr for Rational {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let t = s.trim();
+
+        if let Some((numerator, denominator)) = t.split_once('/') {
+            let numerator: isize = parse_token(s, numerator.trim(), "an integer numerator")?;
+            let denominator_str = denominator.trim();
+            let denominator: isize = parse_token(s, denominator_str, "an integer denominator")?;
+            if denominator == 0 {
+                return Err(ParseError::at(
+                    s,
+                    denominator_str,
+                    "expected a non-zero denominator",
+                ));
+            }
+            return Ok(Self::new(numerator, denominator));
+        }
+
+        let Some((whole, fraction)) = t.split_once('.') else {
+            return Ok(Self::from(parse_token::<isize>(
+                s,
+                t,
+                "a rational like 3/4 or -1.25",
+            )?));
+        };
+
+        let negative = whole.starts_with('-');
+        let whole_digits = whole.strip_prefix(['-', '+']).unwrap_or(whole);
+        // the sign has already been taken off, so there mustn't be another
+        if !whole_digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ParseError::at(
+                s,
+                whole_digits,
+                "expected digits before the decimal point",
+            ));
+        }
+        let whole: isize = if whole_digits.is_empty() {
+            0
+        } else {
+            parse_token(s, whole_digits, "digits before the decimal point")?
+        };
+        if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ParseError::at(
+                s,
+                fraction,
+                "expected digits after the decimal point",
+            ));
+        }
+
+        let too_big = || ParseError::at(s, t, "expected a decimal that fits in an isize");
+        let denominator = 10isize
+            .checked_pow(fraction.len() as u32)
+            .ok_or_else(too_big)?;
+        let fraction: isize = parse_token(s, fraction, "digits after the decimal point")?;
+        let numerator = whole
+            .checked_mul(denominator)
+            .and_then(|whole| whole.checked_add(fraction))
+            .ok_or_else(too_big)?;
+
+        Ok(Self::new(
+            if negative { -numerator } else { numerator },
+            denominator,
+        ))
+    }
+}
+
 impl fmt::Debug for Rational {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(self, f)
@@ -76,22 +151,196 @@ impl Rational {
         }
     }
 
+    // the denominator is always positive, so euclidean division rounds down
     pub fn floor(&self) -> isize {
-        (self.numerator as f64 / self.denominator as f64).floor() as isize
+        self.numerator.div_euclid(self.denominator)
     }
 
     pub fn ceil(&self) -> isize {
-        (self.numerator as f64 / self.denominator as f64).ceil() as isize
+        let floor = self.floor();
+        if self.denominator == 1 {
+            floor
+        } else {
+            floor + 1
+        }
+    }
+
+    // rounds towards zero
+    pub fn trunc(&self) -> isize {
+        self.numerator / self.denominator
+    }
+
+    // rounds to the nearest integer, with halves going away from zero
+    pub fn round(&self) -> isize {
+        let trunc = self.trunc();
+        let remainder = (self.numerator % self.denominator).unsigned_abs();
+        if remainder as u128 * 2 >= self.denominator as u128 {
+            trunc + self.numerator.signum()
+        } else {
+            trunc
+        }
+    }
+
+    pub fn signum(&self) -> Self {
+        Self::from(self.numerator.signum())
+    }
+
+    pub fn recip(&self) -> Self {
+        assert!(self.numerator != 0, "zero has no reciprocal");
+        Self::new(self.denominator, self.numerator)
+    }
+
+    pub fn pow(&self, exponent: i32) -> Self {
+        if exponent < 0 {
+            return self.recip().pow(-exponent);
+        }
+        let exponent = exponent as u32;
+        Self::new(self.numerator.pow(exponent), self.denominator.pow(exponent))
+    }
+
+    // reduces by the gcd of the denominators first so intermediate values stay small
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        let gcd = gcd(self.denominator, other.denominator);
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator / gcd)?
+            .checked_add(other.numerator.checked_mul(self.denominator / gcd)?)?;
+        let denominator = self.denominator.checked_mul(other.denominator / gcd)?;
+        Some(Self::new(numerator, denominator))
+    }
+
+    pub fn checked_sub(&self, other: Self) -> Option<Self> {
+        self.checked_add(Self {
+            numerator: other.numerator.checked_neg()?,
+            ..other
+        })
+    }
+
+    // cross reduces before multiplying so intermediate values stay small
+    pub fn checked_mul(&self, other: Self) -> Option<Self> {
+        if self.numerator == 0 || other.numerator == 0 {
+            return Some(Self::from(0));
+        }
+        let a = gcd(self.numerator.abs(), other.denominator);
+        let b = gcd(other.numerator.abs(), self.denominator);
+        let numerator = (self.numerator / a).checked_mul(other.numerator / b)?;
+        let denominator = (self.denominator / b).checked_mul(other.denominator / a)?;
+        Some(Self::new(numerator, denominator))
+    }
+
+    // None when dividing by zero as well as on overflow
+    pub fn checked_div(&self, other: Self) -> Option<Self> {
+        if other.numerator == 0 {
+            return None;
+        }
+        let recip = Self::new(other.denominator, other.numerator);
+        self.checked_mul(recip)
     }
 
     pub fn is_non_negative_integer(&self) -> bool {
         self.denominator == 1 && self.numerator >= 0
     }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    // the closest rational to value with a denominator of at most max_denominator,
+    // found from the continued fraction expansion of value. None if value is not
+    // finite or its integer part doesn't fit in an isize
+    pub fn approximate(value: f64, max_denominator: isize) -> Option<Self> {
+        assert!(max_denominator > 0, "max_denominator must be positive");
+        if !value.is_finite() || value.abs() >= isize::MAX as f64 {
+            return None;
+        }
+
+        // successive convergents h/k, starting from the two seeds 0/1 and 1/0
+        let (mut h0, mut k0, mut h1, mut k1) = (0isize, 1isize, 1isize, 0isize);
+        let mut x = value;
+
+        loop {
+            let a = x.floor();
+            let a_int = a as isize;
+            let next = a_int
+                .checked_mul(h1)
+                .and_then(|ah| ah.checked_add(h0))
+                .zip(a_int.checked_mul(k1).and_then(|ak| ak.checked_add(k0)));
+
+            let Some((h2, k2)) = next.filter(|(_, k2)| *k2 <= max_denominator) else {
+                // the next convergent is out of reach, but the best semiconvergent
+                // between the last two might still beat the last convergent
+                if k1 == 0 {
+                    return Some(Self::from(a_int));
+                }
+                let t = (max_denominator - k0) / k1;
+                let semi = Self::new(t * h1 + h0, t * k1 + k0);
+                let last = Self::new(h1, k1);
+                let error = |r: &Self| (r.to_f64() - value).abs();
+                return Some(if t > 0 && error(&semi) < error(&last) {
+                    semi
+                } else {
+                    last
+                });
+            };
+
+            (h0, k0, h1, k1) = (h1, k1, h2, k2);
+            let fraction = x - a;
+            if fraction == 0.0 || h1 as f64 / k1 as f64 == value {
+                return Some(Self::new(h1, k1));
+            }
+            x = 1.0 / fraction;
+        }
+    }
+
+    // like 1 3/4 or -2 1/3, or just the integer or fraction if the other part is zero
+    pub fn to_mixed_string(&self) -> String {
+        let whole = self.numerator / self.denominator;
+        let remainder = (self.numerator % self.denominator).abs();
+        match (whole, remainder) {
+            (_, 0) => format!("{whole}"),
+            (0, _) => format!("{self}"),
+            _ => format!("{whole} {remainder}/{}", self.denominator),
+        }
+    }
+
+    // the decimal expansion with any repeating digits in brackets, like 0.1(6) for 1/6.
+    // a cycle can be almost as long as the denominator, so after max_digits decimal
+    // places the rest is cut off with "...", like 0.0000009999... for 1/1000003 and 10
+    pub fn to_decimal_string(&self, max_digits: usize) -> String {
+        let sign = if self.numerator < 0 { "-" } else { "" };
+        // widen so multiplying the remainder by 10 can't overflow
+        let numerator = self.numerator.unsigned_abs() as u128;
+        let denominator = self.denominator.unsigned_abs() as u128;
+        let whole = numerator / denominator;
+        let mut remainder = numerator % denominator;
+        if remainder == 0 {
+            return format!("{sign}{whole}");
+        }
+
+        // long division until the remainder repeats, which marks where the cycle starts
+        let mut digits = String::new();
+        let mut seen: HashMap<u128, usize> = HashMap::new();
+        while remainder != 0 {
+            if let Some(start) = seen.insert(remainder, digits.len()) {
+                return format!("{sign}{whole}.{}({})", &digits[..start], &digits[start..]);
+            }
+            if digits.len() == max_digits {
+                return format!("{sign}{whole}.{digits}...");
+            }
+            remainder *= 10;
+            digits.push(char::from(b'0' + (remainder / denominator) as u8));
+            remainder %= denominator;
+        }
+
+        format!("{sign}{whole}.{digits}")
+    }
 }
 
 impl Ord for Rational {
     fn cmp(&self, other: &Self) -> Ordering {
-        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+        // widen so the cross multiplication can't overflow
+        (self.numerator as i128 * other.denominator as i128)
+            .cmp(&(other.numerator as i128 * self.denominator as i128))
     }
 }
 
@@ -292,5 +541,133 @@ mod tests {
     fn floor_and_ceil() {
         assert_eq!(Rational::new(1, 3).floor(), 0);
         assert_eq!(Rational::new(1, 3).ceil(), 1);
+        assert_eq!(Rational::new(-1, 3).floor(), -1);
+        assert_eq!(Rational::new(-1, 3).ceil(), 0);
+        assert_eq!(Rational::from(-4).floor(), -4);
+        assert_eq!(Rational::from(-4).ceil(), -4);
+
+        // too big to survive a round trip through f64
+        let big = Rational::new(isize::MAX - 2, 2);
+        assert_eq!(big.floor(), isize::MAX / 2 - 1);
+        assert_eq!(big.ceil(), isize::MAX / 2);
+    }
+
+    #[test]
+    fn round_and_trunc() {
+        assert_eq!(Rational::new(7, 2).round(), 4);
+        assert_eq!(Rational::new(-7, 2).round(), -4);
+        assert_eq!(Rational::new(-5, 3).round(), -2);
+        assert_eq!(Rational::new(4, 3).round(), 1);
+        assert_eq!(Rational::new(-7, 2).trunc(), -3);
+        assert_eq!(Rational::new(7, 2).trunc(), 3);
+    }
+
+    #[test]
+    fn recip_pow_and_signum() {
+        assert_eq!(Rational::new(-2, 3).recip(), Rational::new(-3, 2));
+        assert_eq!(Rational::new(-2, 3).pow(3), Rational::new(-8, 27));
+        assert_eq!(Rational::new(2, 3).pow(-2), Rational::new(9, 4));
+        assert_eq!(Rational::new(2, 3).pow(0), Rational::from(1));
+        assert_eq!(Rational::new(-2, 3).signum(), Rational::from(-1));
+        assert_eq!(Rational::from(0).signum(), Rational::from(0));
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let half = Rational::new(1, 2);
+        let max = Rational::from(isize::MAX);
+        assert_eq!(half.checked_add(half), Some(Rational::from(1)));
+        assert_eq!(max.checked_add(half), None);
+        assert_eq!(
+            half.checked_sub(Rational::new(3, 2)),
+            Some(Rational::from(-1))
+        );
+        assert_eq!(half.checked_sub(max), None);
+        assert_eq!(max.checked_mul(Rational::from(2)), None);
+        // cross reduction keeps this in range even though max * 2 overflows
+        assert_eq!(
+            max.checked_mul(Rational::new(2, isize::MAX)),
+            Some(Rational::from(2))
+        );
+        assert_eq!(half.checked_div(Rational::from(0)), None);
+        assert_eq!(half.checked_div(half), Some(Rational::from(1)));
+    }
+
+    #[test]
+    fn hash_min_and_max() {
+        let counts: HashMap<Rational, usize> = [Rational::new(1, 2), Rational::new(2, 4)]
+            .into_iter()
+            .fold(HashMap::new(), |mut counts, r| {
+                *counts.entry(r).or_default() += 1;
+                counts
+            });
+        assert_eq!(counts[&Rational::new(3, 6)], 2);
+
+        let (a, b) = (Rational::new(1, 3), Rational::new(1, 2));
+        assert_eq!(a.min(b), a);
+        assert_eq!(a.max(b), b);
+        // comparing doesn't overflow even when cross multiplying would
+        assert!(Rational::new(isize::MAX, 3) > Rational::new(isize::MAX - 1, 3));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("3/4".parse::<Rational>().unwrap(), Rational::new(3, 4));
+        assert_eq!(
+            " -6 / 8 ".parse::<Rational>().unwrap(),
+            Rational::new(-3, 4)
+        );
+        assert_eq!("-1.25".parse::<Rational>().unwrap(), Rational::new(-5, 4));
+        assert_eq!("-.5".parse::<Rational>().unwrap(), Rational::new(-1, 2));
+        assert_eq!("12".parse::<Rational>().unwrap(), Rational::from(12));
+
+        assert!("1/0".parse::<Rational>().is_err());
+        assert!("1.".parse::<Rational>().is_err());
+        assert!("1.2.3".parse::<Rational>().is_err());
+        assert!("0.00000000000000000001".parse::<Rational>().is_err());
+        // only one sign is allowed
+        assert!("--1.5".parse::<Rational>().is_err());
+        assert!("+-1.5".parse::<Rational>().is_err());
+        assert!("-+1.5".parse::<Rational>().is_err());
+    }
+
+    #[test]
+    fn to_and_from_f64() {
+        assert_eq!(Rational::new(-5, 4).to_f64(), -1.25);
+        assert_eq!(Rational::approximate(0.1, 1000), Some(Rational::new(1, 10)));
+        assert_eq!(
+            Rational::approximate(std::f64::consts::PI, 1000),
+            Some(Rational::new(355, 113))
+        );
+        assert_eq!(
+            Rational::approximate(0.333333333, 100),
+            Some(Rational::new(1, 3))
+        );
+        assert_eq!(Rational::approximate(-2.5, 1), Some(Rational::from(-3)));
+        assert_eq!(Rational::approximate(f64::NAN, 10), None);
+    }
+
+    #[test]
+    fn mixed_and_decimal_strings() {
+        assert_eq!(Rational::new(7, 4).to_mixed_string(), "1 3/4");
+        assert_eq!(Rational::new(-7, 3).to_mixed_string(), "-2 1/3");
+        assert_eq!(Rational::new(-1, 3).to_mixed_string(), "-1/3");
+        assert_eq!(Rational::from(4).to_mixed_string(), "4");
+
+        assert_eq!(Rational::new(1, 4).to_decimal_string(10), "0.25");
+        assert_eq!(Rational::new(1, 6).to_decimal_string(10), "0.1(6)");
+        assert_eq!(Rational::new(-22, 7).to_decimal_string(10), "-3.(142857)");
+        assert_eq!(Rational::from(-2).to_decimal_string(10), "-2");
+
+        // long cycles are cut off, and big denominators don't overflow
+        assert_eq!(
+            Rational::new(1, 1000003).to_decimal_string(10),
+            "0.0000009999..."
+        );
+        assert_eq!(Rational::new(1, 3).to_decimal_string(1), "0.(3)");
+        assert_eq!(
+            Rational::new(1, isize::MAX - 1).to_decimal_string(20),
+            "0.00000000000000000010..."
+        );
     }
 }
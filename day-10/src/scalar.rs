@@ -0,0 +1,48 @@
+use std::iter::Sum;
+
+use crate::big_rational::BigRational;
+use crate::field::Field;
+use crate::rational::Rational;
+
+// the exact, ordered fields that bounds and integer solutions can be worked out over
+pub trait Scalar: Field + Ord + From<isize> + Sum {
+    // the largest integer not above this value, exactly
+    fn floor(&self) -> Self;
+
+    // the smallest integer not below this value, exactly
+    fn ceil(&self) -> Self;
+
+    fn abs(&self) -> Self;
+
+    fn is_integer(&self) -> bool {
+        *self == self.floor()
+    }
+}
+
+impl Scalar for Rational {
+    fn floor(&self) -> Self {
+        Rational::floor(self).into()
+    }
+
+    fn ceil(&self) -> Self {
+        Rational::ceil(self).into()
+    }
+
+    fn abs(&self) -> Self {
+        Rational::abs(self)
+    }
+}
+
+impl Scalar for BigRational {
+    fn floor(&self) -> Self {
+        BigRational::floor(self).into()
+    }
+
+    fn ceil(&self) -> Self {
+        BigRational::ceil(self).into()
+    }
+
+    fn abs(&self) -> Self {
+        BigRational::abs(self)
+    }
+}